@@ -0,0 +1,150 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, Ident, Index};
+
+/// Implements `Interpolate` for a struct by interpolating every field, so all
+/// fields have to implement `Interpolate` themselves.
+///
+/// ```ignore
+/// #[derive(Clone, Interpolate)]
+/// struct CardStyle {
+///   offset: (f32, f32),
+///   color: Color,
+///   radius: f32,
+/// }
+/// ```
+#[proc_macro_derive(Interpolate)]
+pub fn derive_interpolate(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+  expand(input, "Interpolate", |fields| {
+    let interpolate = fields
+      .iter()
+      .map(|field| quote!(::vide::api::animation::Interpolate::interpolate(a.#field, b.#field, t)));
+    let body = construct(fields, interpolate);
+
+    quote! {
+      fn interpolate(a: Self, b: Self, t: f64) -> Self {
+        #body
+      }
+    }
+  })
+  .unwrap_or_else(Error::into_compile_error)
+  .into()
+}
+
+/// Implements `Additive` for a struct by adding and subtracting every field,
+/// so all fields have to implement `Additive` themselves.
+///
+/// ```ignore
+/// #[derive(Clone, Default, Interpolate, Additive)]
+/// struct Offset {
+///   position: (f32, f32),
+///   rotation: f32,
+/// }
+/// ```
+#[proc_macro_derive(Additive)]
+pub fn derive_additive(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+  expand(input, "Additive", |fields| {
+    let add = construct(
+      fields,
+      fields
+        .iter()
+        .map(|field| quote!(::vide::api::animation::Additive::add(a.#field, b.#field))),
+    );
+    let sub = construct(
+      fields,
+      fields
+        .iter()
+        .map(|field| quote!(::vide::api::animation::Additive::sub(a.#field, b.#field))),
+    );
+
+    quote! {
+      fn add(a: Self, b: Self) -> Self {
+        #add
+      }
+
+      fn sub(a: Self, b: Self) -> Self {
+        #sub
+      }
+    }
+  })
+  .unwrap_or_else(Error::into_compile_error)
+  .into()
+}
+
+/// The fields of a struct, either named or the indices of a tuple struct
+enum StructFields {
+  Named(Vec<Ident>),
+  Unnamed(Vec<Index>),
+  Unit,
+}
+
+impl StructFields {
+  fn iter(&self) -> Box<dyn Iterator<Item = proc_macro2::TokenStream> + '_> {
+    match self {
+      Self::Named(idents) => Box::new(idents.iter().map(|ident| quote!(#ident))),
+      Self::Unnamed(indices) => Box::new(indices.iter().map(|index| quote!(#index))),
+      Self::Unit => Box::new(std::iter::empty()),
+    }
+  }
+}
+
+/// Builds `Self` from one expression per field
+fn construct(
+  fields: &StructFields,
+  values: impl Iterator<Item = proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+  match fields {
+    StructFields::Named(idents) => quote!(Self { #(#idents: #values),* }),
+    StructFields::Unnamed(_) => quote!(Self(#(#values),*)),
+    StructFields::Unit => quote!(Self),
+  }
+}
+
+/// Implements the trait `name` of `vide::api::animation` for a struct, with
+/// the methods `body` generates for its fields. Every type parameter has to
+/// implement the trait as well
+fn expand(
+  input: DeriveInput,
+  name: &str,
+  body: impl FnOnce(&StructFields) -> proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+  let Data::Struct(data) = &input.data else {
+    return Err(Error::new_spanned(
+      &input.ident,
+      format!("{name} can only be derived for structs"),
+    ));
+  };
+
+  let trait_name = Ident::new(name, proc_macro2::Span::call_site());
+  let ident = &input.ident;
+  let mut generics = input.generics.clone();
+  for param in generics.type_params_mut() {
+    param
+      .bounds
+      .push(parse_quote!(::vide::api::animation::#trait_name));
+  }
+  let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+  let fields = match &data.fields {
+    Fields::Named(fields) => StructFields::Named(
+      fields
+        .named
+        .iter()
+        .map(|field| field.ident.clone().unwrap())
+        .collect(),
+    ),
+    Fields::Unnamed(fields) => {
+      StructFields::Unnamed((0..fields.unnamed.len()).map(Index::from).collect())
+    }
+    Fields::Unit => StructFields::Unit,
+  };
+  let body = body(&fields);
+
+  Ok(quote! {
+    impl #impl_generics ::vide::api::animation::#trait_name for #ident #ty_generics #where_clause {
+      #body
+    }
+  })
+}
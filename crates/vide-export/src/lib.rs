@@ -5,8 +5,10 @@ use std::{panic::AssertUnwindSafe, ptr::NonNull};
 use objc2::{rc::Retained, runtime::AnyObject};
 use objc2_av_foundation::{
   AVAssetWriter, AVAssetWriterInput, AVFileTypeMPEG4, AVMediaTypeVideo, AVVideoCodecH264,
-  AVVideoCodecKey, AVVideoCompressionPropertiesKey, AVVideoHeightKey, AVVideoProfileLevelKey,
-  AVVideoWidthKey,
+  AVVideoCodecKey, AVVideoColorPrimariesKey, AVVideoColorPrimaries_ITU_R_709_2,
+  AVVideoColorPropertiesKey, AVVideoCompressionPropertiesKey, AVVideoHeightKey,
+  AVVideoProfileLevelKey, AVVideoTransferFunctionKey, AVVideoTransferFunction_ITU_R_709_2,
+  AVVideoWidthKey, AVVideoYCbCrMatrixKey, AVVideoYCbCrMatrix_ITU_R_709_2,
 };
 use objc2_core_foundation::{CFDictionaryCreate, CFNumber};
 use objc2_core_media::{
@@ -14,14 +16,18 @@ use objc2_core_media::{
   CMVideoFormatDescription, CMVideoFormatDescriptionCreate,
 };
 use objc2_core_video::{
-  kCVPixelBufferHeightKey, kCVPixelBufferPixelFormatTypeKey,
-  kCVPixelBufferPoolMinimumBufferCountKey, kCVPixelBufferWidthKey, kCVPixelFormatType_24RGB,
-  kCVReturnSuccess, CVPixelBufferGetBaseAddress, CVPixelBufferLockBaseAddress,
-  CVPixelBufferLockFlags, CVPixelBufferPool, CVPixelBufferPoolCreate,
+  kCVImageBufferColorPrimariesKey, kCVImageBufferColorPrimaries_ITU_R_709_2,
+  kCVImageBufferTransferFunctionKey, kCVImageBufferTransferFunction_ITU_R_709_2,
+  kCVImageBufferYCbCrMatrixKey, kCVImageBufferYCbCrMatrix_ITU_R_709_2, kCVPixelBufferHeightKey,
+  kCVPixelBufferPixelFormatTypeKey, kCVPixelBufferPoolMinimumBufferCountKey,
+  kCVPixelBufferWidthKey, kCVPixelFormatType_420YpCbCr8BiPlanarFullRange,
+  kCVPixelFormatType_420YpCbCr8BiPlanarVideoRange, kCVReturnSuccess, CVAttachmentMode,
+  CVBufferSetAttachment, CVPixelBufferGetBaseAddressOfPlane, CVPixelBufferGetBytesPerRowOfPlane,
+  CVPixelBufferLockBaseAddress, CVPixelBufferLockFlags, CVPixelBufferPool, CVPixelBufferPoolCreate,
   CVPixelBufferPoolCreatePixelBuffer, CVPixelBufferUnlockBaseAddress,
 };
 use objc2_foundation::{ns_string, NSDictionary, NSNumber, NSString, NSURL};
-use vide::io::Export;
+use vide::io::{rgba_to_nv12, ColorRange, Export};
 
 pub struct AVFoundationExporter {
   output: String,
@@ -32,6 +38,7 @@ pub struct AVFoundationExporter {
   current_timestamp: i64,
   ms_per_frame: i64,
   resolution: (usize, usize),
+  range: ColorRange,
 }
 
 impl AVFoundationExporter {
@@ -49,6 +56,21 @@ impl AVFoundationExporter {
       current_timestamp: 0,
       ms_per_frame: 0,
       resolution: (1920, 1080),
+      range: ColorRange::Limited,
+    }
+  }
+
+  /// Writes full range YUV instead of the default limited range
+  pub fn with_range(mut self, range: ColorRange) -> Self {
+    self.range = range;
+    self
+  }
+
+  /// 4:2:0 YUV with a luma and an interleaved chroma plane, in BT.709
+  fn pixel_format(&self) -> u32 {
+    match self.range {
+      ColorRange::Limited => kCVPixelFormatType_420YpCbCr8BiPlanarVideoRange,
+      ColorRange::Full => kCVPixelFormatType_420YpCbCr8BiPlanarFullRange,
     }
   }
 }
@@ -61,6 +83,7 @@ impl Export for AVFoundationExporter {
       settings.resolution.1 as usize,
     );
     let ms_per_frame = ((1.0 / settings.fps) * 1000000.0) as i64;
+    let pixel_format = self.pixel_format();
 
     match objc2::exception::catch(AssertUnwindSafe(|| {
       let url = unsafe { NSURL::fileURLWithPath(&NSString::from_str(&output_path)) };
@@ -76,6 +99,7 @@ impl Export for AVFoundationExporter {
             AVVideoWidthKey.unwrap(),
             AVVideoHeightKey.unwrap(),
             AVVideoCompressionPropertiesKey.unwrap(),
+            AVVideoColorPropertiesKey.unwrap(),
           ],
           &[
             AVVideoCodecH264.unwrap(),
@@ -85,6 +109,19 @@ impl Export for AVFoundationExporter {
               &[AVVideoProfileLevelKey.unwrap()],
               &[ns_string!("H264_Main_AutoLevel")],
             ),
+            // Tags the video, so players know how to convert it back to RGB
+            &NSDictionary::from_slices(
+              &[
+                AVVideoColorPrimariesKey.unwrap(),
+                AVVideoTransferFunctionKey.unwrap(),
+                AVVideoYCbCrMatrixKey.unwrap(),
+              ],
+              &[
+                AVVideoColorPrimaries_ITU_R_709_2.unwrap(),
+                AVVideoTransferFunction_ITU_R_709_2.unwrap(),
+                AVVideoYCbCrMatrix_ITU_R_709_2.unwrap(),
+              ],
+            ),
           ],
         )
       };
@@ -102,7 +139,7 @@ impl Export for AVFoundationExporter {
       let result = unsafe {
         CMVideoFormatDescriptionCreate(
           None,
-          kCVPixelFormatType_24RGB,
+          pixel_format,
           settings.resolution.0 as i32,
           settings.resolution.1 as i32,
           None,
@@ -147,7 +184,7 @@ impl Export for AVFoundationExporter {
         let values = [
           NSNumber::numberWithInt(resolution.0 as i32),
           NSNumber::numberWithInt(resolution.1 as i32),
-          NSNumber::numberWithInt(kCVPixelFormatType_24RGB as i32),
+          NSNumber::numberWithInt(pixel_format as i32),
         ];
 
         CFDictionaryCreate(
@@ -197,6 +234,8 @@ impl Export for AVFoundationExporter {
     let current_timestamp = self.current_timestamp;
     let ms_per_frame = self.ms_per_frame;
     let pool = self.pixel_buffer_pool.as_ref().unwrap();
+    let (width, height) = self.resolution;
+    let range = self.range;
 
     match objc2::exception::catch(AssertUnwindSafe(|| {
       let mut pixel_buffer_out = std::ptr::null_mut();
@@ -209,16 +248,42 @@ impl Export for AVFoundationExporter {
 
       let pixel_buffer = unsafe { Retained::from_raw(pixel_buffer_out).unwrap() };
 
-      let rgb_data = frame
-        .chunks(4)
-        .flat_map(|p| [p[0], p[1], p[2]])
-        .collect::<Vec<u8>>();
+      let (y_plane, uv_plane) = rgba_to_nv12(frame, width, height, range);
 
       unsafe {
         CVPixelBufferLockBaseAddress(&pixel_buffer, CVPixelBufferLockFlags::empty());
-        let pixel_buffer_ptr = CVPixelBufferGetBaseAddress(&pixel_buffer);
-        std::ptr::copy_nonoverlapping(rgb_data.as_ptr(), pixel_buffer_ptr.cast(), rgb_data.len());
+        // Rows of the pixel buffer might be padded
+        for (plane, data, row_length) in
+          [(0, &y_plane, width), (1, &uv_plane, width.div_ceil(2) * 2)]
+        {
+          let plane_ptr = CVPixelBufferGetBaseAddressOfPlane(&pixel_buffer, plane).cast::<u8>();
+          let bytes_per_row = CVPixelBufferGetBytesPerRowOfPlane(&pixel_buffer, plane);
+          for (row, row_data) in data.chunks(row_length).enumerate() {
+            std::ptr::copy_nonoverlapping(
+              row_data.as_ptr(),
+              plane_ptr.add(row * bytes_per_row),
+              row_data.len(),
+            );
+          }
+        }
         CVPixelBufferUnlockBaseAddress(&pixel_buffer, CVPixelBufferLockFlags::empty());
+
+        for (key, value) in [
+          (
+            kCVImageBufferColorPrimariesKey,
+            kCVImageBufferColorPrimaries_ITU_R_709_2,
+          ),
+          (
+            kCVImageBufferTransferFunctionKey,
+            kCVImageBufferTransferFunction_ITU_R_709_2,
+          ),
+          (
+            kCVImageBufferYCbCrMatrixKey,
+            kCVImageBufferYCbCrMatrix_ITU_R_709_2,
+          ),
+        ] {
+          CVBufferSetAttachment(&pixel_buffer, key, value, CVAttachmentMode::ShouldPropagate);
+        }
       }
 
       let mut timing_info = unsafe {
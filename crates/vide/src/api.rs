@@ -1,9 +1,18 @@
 pub mod animation;
+pub mod choreography;
 pub mod color;
+pub mod effect;
+pub mod fullscreen;
 pub mod instance;
+pub mod matte;
 pub mod mesh;
+pub mod noise;
+pub mod path;
 pub mod rect;
+pub mod sequence;
 pub mod shader;
+pub mod shader_clip;
+pub mod time_remap;
 pub mod transform;
 pub mod vertex;
 pub mod video;
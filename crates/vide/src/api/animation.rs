@@ -1,5 +1,11 @@
-use self::ease::{EasingFunction, LINEAR};
-use crate::clip::IntoFrame;
+use std::{cell::Cell, fmt, ops::Range, rc::Rc};
+
+use cgmath::{BaseFloat, Point2, Point3, Quaternion, Vector2, Vector3, Vector4};
+pub use vide_derive::{Additive, Interpolate};
+
+use self::ease::Easing;
+use super::{choreography::Timed, noise::Noise};
+use crate::{clip::IntoFrame, render::Time};
 
 #[macro_export]
 macro_rules! lerp {
@@ -11,9 +17,7 @@ macro_rules! lerp {
 #[macro_export]
 macro_rules! cubic_bezier {
   ($x1:expr, $y1:expr, $x2:expr, $y2:expr) => {
-    |t| t /* TODO: figure this out */ /* lerp!(lerp!(0.0, $x1, t), lerp!($x2,
-                                       * 1.0, t), t), lerp!(lerp!(0.0, $y1, t),
-                                       * lerp!($y2, 1.0, t), t) */
+    |t| $crate::api::animation::ease::cubic_bezier_at($x1, $y1, $x2, $y2, t)
   };
 }
 
@@ -34,15 +38,49 @@ macro_rules! impl_interpolate {
   ($typ:ty) => {
     impl Interpolate for $typ {
       fn interpolate(a: Self, b: Self, t: f64) -> Self {
-        ((b - a) as f64 * t) as $typ + a
+        // Computed as floats, `t` leaves 0.0..=1.0 when overshooting
+        ((b as f64 - a as f64) * t + a as f64) as $typ
       }
     }
   };
 }
 
 pub mod ease {
+  use std::{
+    f64::consts::{FRAC_PI_2, PI},
+    fmt,
+    ops::Deref,
+    rc::Rc,
+  };
+
   pub type EasingFunction = fn(f64) -> f64;
 
+  /// An easing curve mapping the progress of a keyframe (`0.0..=1.0`) to the
+  /// progress of its value. Created from any `Fn(f64) -> f64`, so easings can
+  /// capture state
+  #[derive(Clone)]
+  pub struct Easing(Rc<dyn Fn(f64) -> f64>);
+
+  impl<F: Fn(f64) -> f64 + 'static> From<F> for Easing {
+    fn from(function: F) -> Self {
+      Self(Rc::new(function))
+    }
+  }
+
+  impl Deref for Easing {
+    type Target = dyn Fn(f64) -> f64;
+
+    fn deref(&self) -> &Self::Target {
+      &*self.0
+    }
+  }
+
+  impl fmt::Debug for Easing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      f.write_str("Easing")
+    }
+  }
+
   /// `f(t)=t`
   pub const LINEAR: EasingFunction = |t| t;
   /// `f(t)=t^2`
@@ -53,38 +91,775 @@ pub mod ease {
   pub const IN_QUARTIC: EasingFunction = |t| t * t * t * t;
   /// `f(t)=t^5`
   pub const IN_QUINTIC: EasingFunction = |t| t * t * t * t * t;
-  /// `f(t)=t^10`
-  pub const IN_EXPONENTIAL: EasingFunction = |t| t * t * t * t * t * t;
-  /// `f(t)=t^2`
-  pub const OUT_QUADRATIC: EasingFunction = |t| 1.0 - (1.0 - t).powi(2);
-  /// `f(t)=t^3`
-  pub const OUT_CUBIC: EasingFunction = |t| 1.0 - (1.0 - t).powi(3);
-  /// `f(t)=t^4`
-  pub const OUT_QUARTIC: EasingFunction = |t| 1.0 - (1.0 - t).powi(4);
-  /// `f(t)=t^5`
-  pub const OUT_QUINTIC: EasingFunction = |t| 1.0 - (1.0 - t).powi(5);
-  /// `f(t)=t^10`
-  pub const OUT_EXPONENTIAL: EasingFunction = |t| 1.0 - (1.0 - t).powi(10);
-  /// Overshoots, catapult-ish motion
-  pub const IN_BACK: EasingFunction = cubic_bezier!(0.69, -0.53, 0.06, 0.99);
+  /// `f(t)=2^(10t-10)`, starting at exactly `0.0`
+  pub const IN_EXPONENTIAL: EasingFunction = |t| {
+    if t <= 0.0 {
+      0.0
+    } else {
+      2f64.powf(10.0 * t - 10.0)
+    }
+  };
+  /// Quarter of a cosine wave
+  pub const IN_SINE: EasingFunction = |t| 1.0 - (t * FRAC_PI_2).cos();
+  /// Quarter of a circle
+  pub const IN_CIRCULAR: EasingFunction = |t| 1.0 - (1.0 - t * t).max(0.0).sqrt();
+  /// Pulls back a little before moving, catapult-ish motion
+  pub const IN_BACK: EasingFunction = |t| BACK_C3 * t * t * t - BACK_C1 * t * t;
+  /// Wobbles with growing amplitude before snapping to the end
+  pub const IN_ELASTIC: EasingFunction = |t| {
+    if t <= 0.0 || t >= 1.0 {
+      t.clamp(0.0, 1.0)
+    } else {
+      -(2f64.powf(10.0 * t - 10.0)) * ((t * 10.0 - 10.75) * ELASTIC_C4).sin()
+    }
+  };
+  /// Bounces with growing height before reaching the end
+  pub const IN_BOUNCE: EasingFunction = |t| 1.0 - OUT_BOUNCE(1.0 - t);
+
+  /// `f(t)=1-(1-t)^2`
+  pub const OUT_QUADRATIC: EasingFunction = |t| out(IN_QUADRATIC, t);
+  /// `f(t)=1-(1-t)^3`
+  pub const OUT_CUBIC: EasingFunction = |t| out(IN_CUBIC, t);
+  /// `f(t)=1-(1-t)^4`
+  pub const OUT_QUARTIC: EasingFunction = |t| out(IN_QUARTIC, t);
+  /// `f(t)=1-(1-t)^5`
+  pub const OUT_QUINTIC: EasingFunction = |t| out(IN_QUINTIC, t);
+  /// `f(t)=1-2^(-10t)`, ending at exactly `1.0`
+  pub const OUT_EXPONENTIAL: EasingFunction = |t| out(IN_EXPONENTIAL, t);
+  pub const OUT_SINE: EasingFunction = |t| out(IN_SINE, t);
+  pub const OUT_CIRCULAR: EasingFunction = |t| out(IN_CIRCULAR, t);
   /// Overshoots at end
-  pub const OUT_BACK: EasingFunction = cubic_bezier!(0.42, 1.5, 0.35, 1.0);
+  pub const OUT_BACK: EasingFunction = |t| out(IN_BACK, t);
+  pub const OUT_ELASTIC: EasingFunction = |t| out(IN_ELASTIC, t);
+  /// Falls towards the end and bounces off of it, like a dropped ball
+  pub const OUT_BOUNCE: EasingFunction = |t| {
+    const N: f64 = 7.5625;
+    const D: f64 = 2.75;
+
+    if t < 1.0 / D {
+      N * t * t
+    } else if t < 2.0 / D {
+      let t = t - 1.5 / D;
+      N * t * t + 0.75
+    } else if t < 2.5 / D {
+      let t = t - 2.25 / D;
+      N * t * t + 0.9375
+    } else {
+      let t = t - 2.625 / D;
+      N * t * t + 0.984375
+    }
+  };
+
+  pub const IN_OUT_QUADRATIC: EasingFunction = |t| in_out(IN_QUADRATIC, t);
+  pub const IN_OUT_CUBIC: EasingFunction = |t| in_out(IN_CUBIC, t);
+  pub const IN_OUT_QUARTIC: EasingFunction = |t| in_out(IN_QUARTIC, t);
+  pub const IN_OUT_QUINTIC: EasingFunction = |t| in_out(IN_QUINTIC, t);
+  pub const IN_OUT_EXPONENTIAL: EasingFunction = |t| in_out(IN_EXPONENTIAL, t);
+  pub const IN_OUT_SINE: EasingFunction = |t| in_out(IN_SINE, t);
+  pub const IN_OUT_CIRCULAR: EasingFunction = |t| in_out(IN_CIRCULAR, t);
   /// Overshoots at both sides of animation
-  pub const IN_OUT_BACK: EasingFunction = cubic_bezier!(0.84, -0.43, 0.11, 1.29);
+  pub const IN_OUT_BACK: EasingFunction = |t| {
+    // Penner's in-out variant overshoots more than two halves of `IN_BACK`
+    const C2: f64 = BACK_C1 * 1.525;
+    let back = |t: f64| t * t * ((C2 + 1.0) * t - C2);
 
-  pub const IN_OUT_QUINTIC: EasingFunction = |t| {
     if t < 0.5 {
-      16.0 * t * t * t * t * t
+      back(2.0 * t) / 2.0
+    } else {
+      1.0 - back(2.0 - 2.0 * t) / 2.0
+    }
+  };
+  pub const IN_OUT_ELASTIC: EasingFunction = |t| {
+    const C5: f64 = 2.0 * PI / 4.5;
+
+    if t <= 0.0 || t >= 1.0 {
+      t.clamp(0.0, 1.0)
+    } else if t < 0.5 {
+      -(2f64.powf(20.0 * t - 10.0) * ((20.0 * t - 11.125) * C5).sin()) / 2.0
     } else {
-      1.0 - (-2.0 * t + 2.0).powi(5) / 2.0
+      2f64.powf(-20.0 * t + 10.0) * ((20.0 * t - 11.125) * C5).sin() / 2.0 + 1.0
     }
   };
+  pub const IN_OUT_BOUNCE: EasingFunction = |t| in_out(IN_BOUNCE, t);
+
+  /// How far the back easings pull back
+  const BACK_C1: f64 = 1.70158;
+  const BACK_C3: f64 = BACK_C1 + 1.0;
+  /// Period of the elastic easings
+  const ELASTIC_C4: f64 = 2.0 * PI / 3.0;
+
+  /// Turns an in easing into an out easing
+  #[inline]
+  fn out(ease_in: EasingFunction, t: f64) -> f64 {
+    1.0 - ease_in(1.0 - t)
+  }
+
+  /// Plays an in easing over the first half and its out variant over the
+  /// second half, like [`mirror`]
+  #[inline]
+  fn in_out(ease_in: EasingFunction, t: f64) -> f64 {
+    if t < 0.5 {
+      ease_in(2.0 * t) / 2.0
+    } else {
+      1.0 - ease_in(2.0 - 2.0 * t) / 2.0
+    }
+  }
+
+  /// Where a step easing jumps, like CSS `steps()`
+  #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+  pub enum Jump {
+    /// Jumps at the start of every step, so the value is never `0.0`
+    Start,
+    /// Jumps at the end of every step, so the value is never `1.0` before the
+    /// end
+    #[default]
+    End,
+    /// Starts at `0.0` and ends at `1.0`, jumping in between
+    None,
+    /// Jumps at the start and at the end
+    Both,
+  }
+
+  /// Moves in `steps` discrete jumps instead of smoothly, for a stop-motion
+  /// feel. Ends at `1.0` either way
+  pub fn steps(steps: u32, jump: Jump) -> Easing {
+    let steps = steps.max(1) as f64;
+    let jumps = match jump {
+      Jump::Start | Jump::End => steps,
+      Jump::None => (steps - 1.0).max(1.0),
+      Jump::Both => steps + 1.0,
+    };
+
+    Easing::from(move |t: f64| {
+      if t >= 1.0 {
+        return 1.0;
+      }
+
+      let mut step = (t * steps).floor();
+      if matches!(jump, Jump::Start | Jump::Both) {
+        step += 1.0;
+      }
+
+      (step / jumps).clamp(0.0, 1.0)
+    })
+  }
+
+  /// Plays `easing` backwards, turning in easings into out easings and vice
+  /// versa
+  pub fn reverse(easing: impl Into<Easing>) -> Easing {
+    let easing = easing.into();
+    Easing::from(move |t| 1.0 - easing(1.0 - t))
+  }
+
+  /// Plays `easing` twice as fast, then [`reverse`]d for the second half, e.g.
+  /// turning an in easing into its in-out variant
+  pub fn mirror(easing: impl Into<Easing>) -> Easing {
+    let easing = easing.into();
+    Easing::from(move |t| {
+      if t < 0.5 {
+        easing(2.0 * t) / 2.0
+      } else {
+        1.0 - easing(2.0 - 2.0 * t) / 2.0
+      }
+    })
+  }
+
+  /// Plays `first` until `split` and `second` after it. Both cover their share
+  /// of the progress and of the value, `chain(IN_CUBIC, OUT_BOUNCE, 0.5)`
+  /// speeds up to the middle, then bounces into the end
+  pub fn chain(first: impl Into<Easing>, second: impl Into<Easing>, split: f64) -> Easing {
+    let (first, second) = (first.into(), second.into());
+    let split = split.clamp(0.0, 1.0);
+    Easing::from(move |t| {
+      if t < split {
+        first(t / split) * split
+      } else if split < 1.0 {
+        split + second((t - split) / (1.0 - split)) * (1.0 - split)
+      } else {
+        1.0
+      }
+    })
+  }
+
+  /// Value of the CSS-like cubic bezier curve from `(0, 0)` to `(1, 1)` with
+  /// the control points `(x1, y1)` and `(x2, y2)` at `t`, see
+  /// [`cubic_bezier!`](crate::cubic_bezier). `x1` and `x2` have to be in
+  /// `0.0..=1.0`
+  pub fn cubic_bezier_at(x1: f64, y1: f64, x2: f64, y2: f64, t: f64) -> f64 {
+    let bezier = |a: f64, b: f64, s: f64| {
+      let u = 1.0 - s;
+      3.0 * u * u * s * a + 3.0 * u * s * s * b + s * s * s
+    };
+    let slope = |a: f64, b: f64, s: f64| {
+      let u = 1.0 - s;
+      3.0 * u * u * a + 6.0 * u * s * (b - a) + 3.0 * s * s * (1.0 - b)
+    };
+
+    if t <= 0.0 || t >= 1.0 {
+      return t.clamp(0.0, 1.0);
+    }
+
+    // Find the point of the curve at x = t, Newton's method converges in a
+    // few steps unless the curve is flat, bisection always does
+    let mut s = t;
+    for _ in 0..8 {
+      let error = bezier(x1, x2, s) - t;
+      if error.abs() < 1e-7 {
+        return bezier(y1, y2, s);
+      }
+
+      let slope = slope(x1, x2, s);
+      if slope.abs() < 1e-6 {
+        break;
+      }
+      s -= error / slope;
+    }
+
+    let (mut low, mut high) = (0.0, 1.0);
+    s = t;
+    for _ in 0..64 {
+      let x = bezier(x1, x2, s);
+      if (x - t).abs() < 1e-7 {
+        break;
+      }
+
+      if x < t {
+        low = s;
+      } else {
+        high = s;
+      }
+      s = (low + high) / 2.0;
+    }
+
+    bezier(y1, y2, s)
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+
+    const EASINGS: [(&str, EasingFunction); 31] = [
+      ("LINEAR", LINEAR),
+      ("IN_QUADRATIC", IN_QUADRATIC),
+      ("IN_CUBIC", IN_CUBIC),
+      ("IN_QUARTIC", IN_QUARTIC),
+      ("IN_QUINTIC", IN_QUINTIC),
+      ("IN_EXPONENTIAL", IN_EXPONENTIAL),
+      ("IN_SINE", IN_SINE),
+      ("IN_CIRCULAR", IN_CIRCULAR),
+      ("IN_BACK", IN_BACK),
+      ("IN_ELASTIC", IN_ELASTIC),
+      ("IN_BOUNCE", IN_BOUNCE),
+      ("OUT_QUADRATIC", OUT_QUADRATIC),
+      ("OUT_CUBIC", OUT_CUBIC),
+      ("OUT_QUARTIC", OUT_QUARTIC),
+      ("OUT_QUINTIC", OUT_QUINTIC),
+      ("OUT_EXPONENTIAL", OUT_EXPONENTIAL),
+      ("OUT_SINE", OUT_SINE),
+      ("OUT_CIRCULAR", OUT_CIRCULAR),
+      ("OUT_BACK", OUT_BACK),
+      ("OUT_ELASTIC", OUT_ELASTIC),
+      ("OUT_BOUNCE", OUT_BOUNCE),
+      ("IN_OUT_QUADRATIC", IN_OUT_QUADRATIC),
+      ("IN_OUT_CUBIC", IN_OUT_CUBIC),
+      ("IN_OUT_QUARTIC", IN_OUT_QUARTIC),
+      ("IN_OUT_QUINTIC", IN_OUT_QUINTIC),
+      ("IN_OUT_EXPONENTIAL", IN_OUT_EXPONENTIAL),
+      ("IN_OUT_SINE", IN_OUT_SINE),
+      ("IN_OUT_CIRCULAR", IN_OUT_CIRCULAR),
+      ("IN_OUT_BACK", IN_OUT_BACK),
+      ("IN_OUT_ELASTIC", IN_OUT_ELASTIC),
+      ("IN_OUT_BOUNCE", IN_OUT_BOUNCE),
+    ];
+
+    fn assert_close(actual: f64, expected: f64, name: &str) {
+      assert!(
+        (actual - expected).abs() < 1e-6,
+        "{name}: expected {expected}, got {actual}"
+      );
+    }
+
+    #[test]
+    fn easings_start_at_zero_and_end_at_one() {
+      for (name, easing) in EASINGS {
+        assert_close(easing(0.0), 0.0, name);
+        assert_close(easing(1.0), 1.0, name);
+      }
+    }
+
+    #[test]
+    fn in_out_easings_pass_through_the_middle() {
+      for (name, easing) in EASINGS
+        .iter()
+        .filter(|(name, _)| name.starts_with("IN_OUT"))
+      {
+        assert_close(easing(0.5), 0.5, name);
+      }
+    }
+
+    #[test]
+    fn exponential_easings_are_exponential() {
+      assert_close(IN_EXPONENTIAL(0.5), 2f64.powi(-5), "IN_EXPONENTIAL");
+      assert_close(OUT_EXPONENTIAL(0.5), 1.0 - 2f64.powi(-5), "OUT_EXPONENTIAL");
+    }
+
+    #[test]
+    fn steps_jump_like_css() {
+      let cases = [
+        (Jump::End, [0.0, 0.0, 0.25, 0.75, 1.0]),
+        (Jump::Start, [0.25, 0.25, 0.5, 1.0, 1.0]),
+        (Jump::None, [0.0, 0.0, 1.0 / 3.0, 1.0, 1.0]),
+        (Jump::Both, [0.2, 0.2, 0.4, 0.8, 1.0]),
+      ];
+
+      for (jump, expected) in cases {
+        let easing = steps(4, jump);
+        for (t, expected) in [0.0, 0.2, 0.25, 0.99, 1.0].into_iter().zip(expected) {
+          assert_close(easing(t), expected, &format!("{jump:?} at {t}"));
+        }
+      }
+    }
+
+    #[test]
+    fn combinators_keep_endpoints() {
+      let combined = [
+        ("reverse", reverse(IN_BACK)),
+        ("mirror", mirror(IN_ELASTIC)),
+        ("chain", chain(IN_CUBIC, OUT_BOUNCE, 0.3)),
+        ("chain at start", chain(IN_CUBIC, OUT_BOUNCE, 0.0)),
+        ("chain at end", chain(IN_CUBIC, OUT_BOUNCE, 1.0)),
+      ];
+
+      for (name, easing) in combined {
+        assert_close(easing(0.0), 0.0, name);
+        assert_close(easing(1.0), 1.0, name);
+      }
+    }
+
+    #[test]
+    fn combinators_match_the_builtin_variants() {
+      for t in [0.1, 0.3, 0.5, 0.7, 0.9] {
+        assert_close(reverse(IN_CUBIC)(t), OUT_CUBIC(t), "reverse");
+        assert_close(mirror(IN_SINE)(t), IN_OUT_SINE(t), "mirror");
+        assert_close(IN_OUT_SINE(t), (1.0 - (PI * t).cos()) / 2.0, "in-out");
+        assert_close(
+          chain(IN_QUADRATIC, OUT_QUADRATIC, 0.5)(t),
+          IN_OUT_QUADRATIC(t),
+          "chain",
+        );
+      }
+    }
+
+    #[test]
+    fn cubic_bezier_follows_its_control_points() {
+      let linear: EasingFunction = cubic_bezier!(0.0, 0.0, 1.0, 1.0);
+      let ease: EasingFunction = cubic_bezier!(0.25, 0.1, 0.25, 1.0);
+      for t in [0.0, 0.25, 0.5, 0.75, 1.0] {
+        assert_close(linear(t), t, "linear");
+      }
+
+      assert_close(ease(0.0), 0.0, "ease");
+      assert_close(ease(1.0), 1.0, "ease");
+      // Reference value of CSS `ease` at the middle
+      assert!(
+        (ease(0.5) - 0.8024).abs() < 1e-3,
+        "ease at 0.5: {}",
+        ease(0.5)
+      );
+    }
+  }
+}
+
+/// A damped spring, moving a value towards its target like a physical
+/// spring would.
+///
+/// Springs are solved analytically, so evaluating a frame always yields the
+/// same value no matter which frames were rendered before it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spring {
+  pub stiffness: f64,
+  pub damping: f64,
+  pub mass: f64,
+  /// Velocity at the start of the motion, in distances to the target per
+  /// second. A velocity of `1.0` would cover the whole distance in one second
+  pub velocity: f64,
+}
+
+impl Default for Spring {
+  fn default() -> Self {
+    Self::new(100.0, 10.0, 1.0)
+  }
+}
+
+impl Spring {
+  /// Panics unless `stiffness` and `mass` are positive and `damping` isn't
+  /// negative, the motion isn't defined otherwise
+  pub const fn new(stiffness: f64, damping: f64, mass: f64) -> Self {
+    assert!(stiffness > 0.0, "spring stiffness has to be positive");
+    assert!(damping >= 0.0, "spring damping can't be negative");
+    assert!(mass > 0.0, "spring mass has to be positive");
+
+    Self {
+      stiffness,
+      damping,
+      mass,
+      velocity: 0.0,
+    }
+  }
+
+  pub const fn with_velocity(mut self, velocity: f64) -> Self {
+    self.velocity = velocity;
+    self
+  }
+
+  /// Progress towards the target `seconds` after the spring was released,
+  /// `0.0` at the start and approaching `1.0` as the spring settles. Can leave
+  /// `0.0..=1.0` when the spring overshoots
+  pub fn progress(&self, seconds: f64) -> f64 {
+    if seconds <= 0.0 {
+      return 0.0;
+    }
+
+    // Solves `m*x'' + c*x' + k*x = 0` for the offset from the target `x`,
+    // starting at `x(0) = -1` and `x'(0) = velocity`
+    let x0 = -1.0;
+    let v0 = self.velocity;
+    let omega = (self.stiffness / self.mass).sqrt();
+    let zeta = self.damping / (2.0 * (self.stiffness * self.mass).sqrt());
+
+    let offset = if zeta < 1.0 {
+      // Underdamped, oscillates around the target
+      let omega_d = omega * (1.0 - zeta * zeta).sqrt();
+      let decay = (-zeta * omega * seconds).exp();
+      decay
+        * (x0 * (omega_d * seconds).cos()
+          + (v0 + zeta * omega * x0) / omega_d * (omega_d * seconds).sin())
+    } else if zeta == 1.0 {
+      // Critically damped
+      (-omega * seconds).exp() * (x0 + (v0 + omega * x0) * seconds)
+    } else {
+      // Overdamped
+      let root = (zeta * zeta - 1.0).sqrt();
+      let r1 = -omega * (zeta - root);
+      let r2 = -omega * (zeta + root);
+      let c2 = (v0 - r1 * x0) / (r2 - r1);
+      let c1 = x0 - c2;
+      c1 * (r1 * seconds).exp() + c2 * (r2 * seconds).exp()
+    };
+
+    1.0 + offset
+  }
+}
+
+/// What an animation does after its last keyframe.
+///
+/// Every pass of a repeat lasts as long as the keyframes, from frame `0` up to
+/// the last keyframe. The last keyframe itself is where the next pass starts,
+/// so it is only shown once the repeats are over
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Repeat {
+  /// Stays on the last keyframe
+  #[default]
+  Hold,
+  /// Plays the keyframes this many times in total, then holds the last one
+  Loop(u32),
+  /// Plays the keyframes over and over again
+  LoopForever,
+  /// Plays the keyframes forwards, then backwards, this many times in total
+  /// (each direction counts once), then holds
+  PingPong(u32),
+  /// Plays the keyframes forwards and backwards over and over again
+  PingPongForever,
+  /// Keeps changing with the velocity the value had at the last keyframe,
+  /// stands still when the last keyframe is a hold
+  Extrapolate,
+}
+
+impl Repeat {
+  /// Maps `frame`, which is at or past the last keyframe at `end`, back to the
+  /// frame of the keyframes that is shown
+  fn frame(self, frame: u64, end: u64) -> u64 {
+    let (pass, offset) = (frame / end, frame % end);
+    match self {
+      Self::Hold | Self::Extrapolate => frame,
+      Self::Loop(times) if pass >= times as u64 => end,
+      Self::Loop(_) | Self::LoopForever => offset,
+      Self::PingPong(times) if pass >= times as u64 => {
+        if times % 2 == 0 {
+          0
+        } else {
+          end
+        }
+      }
+      Self::PingPong(_) | Self::PingPongForever => {
+        if pass % 2 == 0 {
+          offset
+        } else {
+          end - offset
+        }
+      }
+    }
+  }
 }
 
+/// Values that can be animated. `t` usually ranges from `0.0` (`a`) to `1.0`
+/// (`b`), but can leave that range when easings or springs overshoot.
+///
+/// Structs can derive it, interpolating every field:
+///
+/// ```
+/// # use vide::prelude::*;
+/// #[derive(Clone, Debug, PartialEq, Interpolate)]
+/// struct CardStyle {
+///   offset: (f32, f32),
+///   color: Color,
+///   radius: f32,
+/// }
+///
+/// #[derive(Clone, Debug, PartialEq, Interpolate)]
+/// struct Opacity(f64);
+///
+/// let a = CardStyle {
+///   offset: (0.0, 10.0),
+///   color: Color::from_raw(0.0, 0.0, 0.0, 1.0),
+///   radius: 4.0,
+/// };
+/// let b = CardStyle {
+///   offset: (20.0, 30.0),
+///   color: Color::from_raw(1.0, 0.5, 0.0, 1.0),
+///   radius: 8.0,
+/// };
+/// assert_eq!(
+///   CardStyle::interpolate(a, b, 0.25),
+///   CardStyle {
+///     offset: (5.0, 15.0),
+///     color: Color::from_raw(0.25, 0.125, 0.0, 1.0),
+///     radius: 5.0,
+///   }
+/// );
+/// assert_eq!(
+///   Opacity::interpolate(Opacity(0.0), Opacity(0.5), 0.5),
+///   Opacity(0.25)
+/// );
+/// ```
 pub trait Interpolate {
   fn interpolate(a: Self, b: Self, t: f64) -> Self;
 }
 
+impl<T: Interpolate, const N: usize> Interpolate for [T; N] {
+  fn interpolate(a: Self, b: Self, t: f64) -> Self {
+    let mut b = b.into_iter();
+    a.map(|a| T::interpolate(a, b.next().unwrap(), t))
+  }
+}
+
+/// Both vectors need to have the same length
+impl<T: Interpolate> Interpolate for Vec<T> {
+  fn interpolate(a: Self, b: Self, t: f64) -> Self {
+    assert_eq!(
+      a.len(),
+      b.len(),
+      "can't interpolate between vectors of different lengths"
+    );
+    a.into_iter()
+      .zip(b)
+      .map(|(a, b)| T::interpolate(a, b, t))
+      .collect()
+  }
+}
+
+/// Interpolates when both are `Some`, otherwise jumps from `a` to `b` halfway
+/// through, like discrete animations in CSS
+impl<T: Interpolate> Interpolate for Option<T> {
+  fn interpolate(a: Self, b: Self, t: f64) -> Self {
+    match (a, b) {
+      (Some(a), Some(b)) => Some(T::interpolate(a, b, t)),
+      (a, _) if t < 0.5 => a,
+      (_, b) => b,
+    }
+  }
+}
+
+macro_rules! impl_interpolate_cgmath {
+  ($typ:ident { $($field:ident),+ }) => {
+    impl<S: Interpolate> Interpolate for $typ<S> {
+      fn interpolate(a: Self, b: Self, t: f64) -> Self {
+        $typ {
+          $($field: S::interpolate(a.$field, b.$field, t)),+
+        }
+      }
+    }
+  };
+}
+
+impl_interpolate_cgmath!(Vector2 { x, y });
+impl_interpolate_cgmath!(Vector3 { x, y, z });
+impl_interpolate_cgmath!(Vector4 { x, y, z, w });
+impl_interpolate_cgmath!(Point2 { x, y });
+impl_interpolate_cgmath!(Point3 { x, y, z });
+
+/// Rotates along the shortest arc with constant speed (slerp)
+impl<S: BaseFloat> Interpolate for Quaternion<S> {
+  fn interpolate(a: Self, b: Self, t: f64) -> Self {
+    a.slerp(b, S::from(t).unwrap())
+  }
+}
+
+/// Values that can be added to and subtracted from each other, needed to stack
+/// [`Blend::Add`] layers on top of an animation. Values made of several
+/// components add them one by one.
+///
+/// Structs can derive it, adding every field:
+///
+/// ```
+/// # use vide::prelude::*;
+/// #[derive(Clone, Debug, Default, PartialEq, Interpolate, Additive)]
+/// struct Offset {
+///   position: (f32, f32),
+///   rotation: f32,
+/// }
+///
+/// let a = Offset {
+///   position: (1.0, 2.0),
+///   rotation: 90.0,
+/// };
+/// let b = Offset {
+///   position: (0.5, -1.0),
+///   rotation: 45.0,
+/// };
+/// assert_eq!(
+///   Offset::add(a.clone(), b.clone()),
+///   Offset {
+///     position: (1.5, 1.0),
+///     rotation: 135.0
+///   }
+/// );
+/// assert_eq!(
+///   Offset::sub(a, b),
+///   Offset {
+///     position: (0.5, 3.0),
+///     rotation: 45.0
+///   }
+/// );
+/// ```
+pub trait Additive {
+  fn add(a: Self, b: Self) -> Self;
+  fn sub(a: Self, b: Self) -> Self;
+}
+
+/// Integers wrap around, so an offset that is subtracted first and added back
+/// later still ends up at the right value
+macro_rules! impl_additive_int {
+  ($($typ:ty),+) => {
+    $(impl Additive for $typ {
+      fn add(a: Self, b: Self) -> Self {
+        a.wrapping_add(b)
+      }
+
+      fn sub(a: Self, b: Self) -> Self {
+        a.wrapping_sub(b)
+      }
+    })+
+  };
+}
+
+macro_rules! impl_additive_float {
+  ($($typ:ty),+) => {
+    $(impl Additive for $typ {
+      fn add(a: Self, b: Self) -> Self {
+        a + b
+      }
+
+      fn sub(a: Self, b: Self) -> Self {
+        a - b
+      }
+    })+
+  };
+}
+
+macro_rules! impl_additive_cgmath {
+  ($typ:ident { $($field:ident),+ }) => {
+    impl<S: Additive> Additive for $typ<S> {
+      fn add(a: Self, b: Self) -> Self {
+        $typ {
+          $($field: S::add(a.$field, b.$field)),+
+        }
+      }
+
+      fn sub(a: Self, b: Self) -> Self {
+        $typ {
+          $($field: S::sub(a.$field, b.$field)),+
+        }
+      }
+    }
+  };
+}
+
+/// `a + (c - b)`, moves `a` by as much as `c` is away from `b`
+fn offset<T: Additive>(a: T, b: T, c: T) -> T {
+  T::add(a, T::sub(c, b))
+}
+
+impl_additive_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+impl_additive_float!(f32, f64);
+impl_additive_cgmath!(Vector2 { x, y });
+impl_additive_cgmath!(Vector3 { x, y, z });
+impl_additive_cgmath!(Vector4 { x, y, z, w });
+impl_additive_cgmath!(Point2 { x, y });
+impl_additive_cgmath!(Point3 { x, y, z });
+
+impl<T: Additive, const N: usize> Additive for [T; N] {
+  fn add(a: Self, b: Self) -> Self {
+    let mut b = b.into_iter();
+    a.map(|a| T::add(a, b.next().unwrap()))
+  }
+
+  fn sub(a: Self, b: Self) -> Self {
+    let mut b = b.into_iter();
+    a.map(|a| T::sub(a, b.next().unwrap()))
+  }
+}
+
+impl<A: Additive, B: Additive> Additive for (A, B) {
+  fn add(a: Self, b: Self) -> Self {
+    (A::add(a.0, b.0), B::add(a.1, b.1))
+  }
+
+  fn sub(a: Self, b: Self) -> Self {
+    (A::sub(a.0, b.0), B::sub(a.1, b.1))
+  }
+}
+
+impl<A: Additive, B: Additive, C: Additive> Additive for (A, B, C) {
+  fn add(a: Self, b: Self) -> Self {
+    (A::add(a.0, b.0), B::add(a.1, b.1), C::add(a.2, b.2))
+  }
+
+  fn sub(a: Self, b: Self) -> Self {
+    (A::sub(a.0, b.0), B::sub(a.1, b.1), C::sub(a.2, b.2))
+  }
+}
+
+impl<A: Additive, B: Additive, C: Additive, D: Additive> Additive for (A, B, C, D) {
+  fn add(a: Self, b: Self) -> Self {
+    (
+      A::add(a.0, b.0),
+      B::add(a.1, b.1),
+      C::add(a.2, b.2),
+      D::add(a.3, b.3),
+    )
+  }
+
+  fn sub(a: Self, b: Self) -> Self {
+    (
+      A::sub(a.0, b.0),
+      B::sub(a.1, b.1),
+      C::sub(a.2, b.2),
+      D::sub(a.3, b.3),
+    )
+  }
+}
+
 impl_interpolate!(u8);
 impl_interpolate!(u16);
 impl_interpolate!(u32);
@@ -140,87 +915,581 @@ where
   }
 }
 
-#[derive(Clone)]
-pub struct Keyframe<T: Interpolate> {
-  pub easing: EasingFunction,
-  pub state: T,
-  pub frame: u64,
+/// Values that have a distance between them, needed to measure how far
+/// [roving](AnimatedBuilder::rove) keyframes travel. Values made of several
+/// components use the euclidean distance
+pub trait Distance {
+  fn distance(a: &Self, b: &Self) -> f64;
 }
 
-impl<T: Interpolate + Clone> Keyframe<T> {
-  pub fn evaluate(&self, previous: Keyframe<T>, frame: u64) -> T {
-    // t: 0.0..=1.0
-    let t = (frame - previous.frame) as f64 / (self.frame - previous.frame) as f64;
-    T::interpolate(previous.state, self.state.clone(), (self.easing)(t))
-  }
+macro_rules! impl_distance {
+  ($($typ:ty),+) => {
+    $(impl Distance for $typ {
+      fn distance(a: &Self, b: &Self) -> f64 {
+        (*b as f64 - *a as f64).abs()
+      }
+    })+
+  };
 }
 
-pub struct Animated<T: Interpolate + Clone> {
-  initial: T,
-  keyframes: Vec<Keyframe<T>>,
+macro_rules! impl_distance_cgmath {
+  ($typ:ident { $($field:ident),+ }) => {
+    impl<S: Distance> Distance for $typ<S> {
+      fn distance(a: &Self, b: &Self) -> f64 {
+        (0.0 $(+ S::distance(&a.$field, &b.$field).powi(2))+).sqrt()
+      }
+    }
+  };
 }
 
-impl<T: Interpolate + Clone> From<T> for Animated<T> {
-  fn from(value: T) -> Self {
-    unanimated!(value)
+impl_distance!(f32, f64);
+impl_distance_cgmath!(Vector2 { x, y });
+impl_distance_cgmath!(Vector3 { x, y, z });
+impl_distance_cgmath!(Point2 { x, y });
+impl_distance_cgmath!(Point3 { x, y, z });
+
+impl<A: Distance, B: Distance> Distance for (A, B) {
+  fn distance(a: &Self, b: &Self) -> f64 {
+    A::distance(&a.0, &b.0).hypot(B::distance(&a.1, &b.1))
   }
 }
 
-impl<T: Interpolate + Clone> Animated<T> {
-  pub fn builder() -> AnimatedBuilder<T> {
-    AnimatedBuilder::default()
+impl<A: Distance, B: Distance, C: Distance> Distance for (A, B, C) {
+  fn distance(a: &Self, b: &Self) -> f64 {
+    (A::distance(&a.0, &b.0).powi(2)
+      + B::distance(&a.1, &b.1).powi(2)
+      + C::distance(&a.2, &b.2).powi(2))
+    .sqrt()
   }
+}
 
-  pub fn push_keyframe(&mut self, keyframe: Keyframe<T>) {
-    self.keyframes.push(keyframe)
-  }
+/// How the value gets from the previous keyframe to a keyframe
+#[derive(Clone, Debug)]
+pub enum Interpolation<T> {
+  /// Keeps the previous value, then jumps to this keyframe once it is reached
+  Hold,
+  /// Moves at a constant rate
+  Linear,
+  /// Follows an easing curve
+  Eased(Easing),
+  /// Moves along a cubic bezier curve instead of a straight line, timed by
+  /// `easing`. Works for any [`Interpolate`] value, e.g. curved motion of a
+  /// position
+  Bezier {
+    /// Control point the curve leaves the previous keyframe towards
+    out_tangent: T,
+    /// Control point the curve arrives at this keyframe from
+    in_tangent: T,
+    easing: Easing,
+  },
+  /// Springs towards this keyframe, see [`AnimatedBuilder::spring`]
+  Spring(Spring),
+}
 
-  pub fn evaluate(&self, frame: u64) -> T {
-    // Fallback when no keyframes
-    if self.keyframes.is_empty() {
-      return self.initial.clone();
-    } else {
-      // When on first keyframe, interpolate with self.initial
-      let keyframe = self.keyframes.first().unwrap();
-      if keyframe.frame >= frame {
-        return keyframe.evaluate(
-          Keyframe {
-            easing: LINEAR,
-            state: self.initial.clone(),
-            frame: 0,
-          },
-          frame,
-        );
-      }
-    }
+/// Interpolates from one keyframe to the next, [`Interpolate::interpolate`]
+/// by default. Created from any `Fn(T, T, f64) -> T`, so mixes can capture
+/// state, see [`AnimatedBuilder::mix`]
+pub struct Mix<T>(Option<MixFunction<T>>);
 
-    // Interpolate between keyframes
-    for keyframes in self.keyframes.windows(2) {
-      let previous = keyframes[0].clone();
-      let keyframe = keyframes[1].clone();
-      if keyframe.frame >= frame {
-        return keyframe.evaluate(previous, frame);
-      }
-    }
+type MixFunction<T> = Rc<dyn Fn(T, T, f64) -> T>;
 
-    // When all keyframes have passed
-    if let Some(keyframe) = self.keyframes.last() {
-      return keyframe.state.clone();
-    }
+impl<T> Default for Mix<T> {
+  fn default() -> Self {
+    Self(None)
+  }
+}
 
-    self.initial.clone() // Fallback
+impl<T> Clone for Mix<T> {
+  fn clone(&self) -> Self {
+    Self(self.0.clone())
   }
 }
 
-impl<T> Default for Animated<T>
-where
-  T: Default + Interpolate + Clone,
-{
-  fn default() -> Self {
-    Self {
-      initial: T::default(),
-      keyframes: Vec::new(),
-    }
+impl<T, F: Fn(T, T, f64) -> T + 'static> From<F> for Mix<T> {
+  fn from(function: F) -> Self {
+    Self(Some(Rc::new(function)))
+  }
+}
+
+impl<T> fmt::Debug for Mix<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("Mix")
+  }
+}
+
+impl<T: Interpolate> Mix<T> {
+  pub fn apply(&self, a: T, b: T, t: f64) -> T {
+    match &self.0 {
+      Some(mix) => mix(a, b, t),
+      None => T::interpolate(a, b, t),
+    }
+  }
+}
+
+#[derive(Clone)]
+pub struct Keyframe<T: Interpolate> {
+  pub interpolation: Interpolation<T>,
+  /// Interpolates from the previous keyframe to this one
+  pub mix: Mix<T>,
+  /// Roving keyframes get retimed so the value changes at a constant speed
+  /// between the surrounding keyframes, see [`AnimatedBuilder::rove`]
+  pub roving: bool,
+  pub state: T,
+  pub frame: u64,
+}
+
+impl<T: Interpolate + Clone> Keyframe<T> {
+  pub fn evaluate(&self, previous: Keyframe<T>, frame: u64) -> T {
+    // Keyframes on the same frame jump straight to the later one
+    let duration = self.frame.saturating_sub(previous.frame);
+    if duration == 0 {
+      return self.state.clone();
+    }
+
+    // t: 0.0..=1.0
+    let t = (frame.saturating_sub(previous.frame) as f64 / duration as f64).min(1.0);
+    match &self.interpolation {
+      Interpolation::Hold if t < 1.0 => previous.state,
+      Interpolation::Hold => self.state.clone(),
+      // Springs are evaluated by the track, they need the keyframes around them
+      Interpolation::Linear | Interpolation::Spring(_) => {
+        self.mix.apply(previous.state, self.state.clone(), t)
+      }
+      Interpolation::Eased(easing) => self
+        .mix
+        .apply(previous.state, self.state.clone(), easing(t)),
+      Interpolation::Bezier {
+        out_tangent,
+        in_tangent,
+        easing,
+      } => self.bezier(previous.state, out_tangent, in_tangent, easing(t)),
+    }
+  }
+
+  /// Point `t` along the bezier curve from `start` to this keyframe
+  fn bezier(&self, start: T, out_tangent: &T, in_tangent: &T, t: f64) -> T {
+    let mix = |a, b, t| self.mix.apply(a, b, t);
+    let a = mix(start, out_tangent.clone(), t);
+    let b = mix(out_tangent.clone(), in_tangent.clone(), t);
+    let c = mix(in_tangent.clone(), self.state.clone(), t);
+    mix(mix(a, b.clone(), t), mix(b, c, t), t)
+  }
+
+  fn spring(&self) -> Option<Spring> {
+    match self.interpolation {
+      Interpolation::Spring(spring) => Some(spring),
+      _ => None,
+    }
+  }
+}
+
+/// Inserts `keyframe` while keeping `keyframes` sorted by frame. Keyframes on
+/// the same frame keep the order they were added in
+fn insert_keyframe<T: Interpolate>(keyframes: &mut Vec<Keyframe<T>>, keyframe: Keyframe<T>) {
+  let index = keyframes.partition_point(|k| k.frame <= keyframe.frame);
+  keyframes.insert(index, keyframe);
+}
+
+/// A value changing over time, either following keyframes or computed by a
+/// function, see [`Animated::builder`] and [`Animated::from_fn`]
+#[derive(Clone)]
+pub struct Animated<T: Interpolate + Clone> {
+  source: Source<T>,
+  /// Applied on top of `source` in order, see [`Animated::layer`]
+  layers: Vec<AnimationLayer<T>>,
+  /// Seconds the animation is shifted by, see [`Timed::delay`]
+  delay: f64,
+}
+
+#[derive(Clone)]
+enum Source<T: Interpolate + Clone> {
+  Keyframes(Track<T>),
+  Function(Rc<dyn Fn(Time) -> T>),
+}
+
+impl<T: Interpolate + Clone> From<T> for Animated<T> {
+  fn from(value: T) -> Self {
+    unanimated!(value)
+  }
+}
+
+impl<T: Interpolate + Clone> Animated<T> {
+  pub fn builder() -> AnimatedBuilder<T> {
+    AnimatedBuilder::default()
+  }
+
+  /// Computes the value on every frame by calling `function`, allowing values
+  /// driven by math, noise or data.
+  ///
+  /// ```
+  /// # use vide::prelude::*;
+  /// let wave = Animated::from_fn(|time: Time| ((time.clip_time * 2.0).sin() * 100.0, 0.0));
+  ///
+  /// let time = Time::new(45, 120, 60.0);
+  /// assert_eq!(wave.evaluate(time), ((1.5f64).sin() * 100.0, 0.0));
+  /// ```
+  pub fn from_fn(function: impl Fn(Time) -> T + 'static) -> Self {
+    Self {
+      source: Source::Function(Rc::new(function)),
+      layers: vec![],
+      delay: 0.0,
+    }
+  }
+
+  /// Adds a keyframe to the end of the animation.
+  ///
+  /// Panics when the animation was created with [`Animated::from_fn`]
+  pub fn push_keyframe(&mut self, keyframe: Keyframe<T>) {
+    match &mut self.source {
+      Source::Keyframes(track) => insert_keyframe(&mut track.keyframes, keyframe),
+      Source::Function(_) => panic!("can't add keyframes to an animation driven by a function"),
+    }
+  }
+
+  /// Evaluates the animation at `time.clip_frame`, keyframes are relative to
+  /// the start of the clip the animation belongs to
+  pub fn evaluate(&self, time: Time) -> T {
+    let time = self.delayed(time);
+    let value = match &self.source {
+      Source::Keyframes(track) => track.evaluate(time.clip_frame),
+      Source::Function(function) => function(time),
+    };
+
+    self
+      .layers
+      .iter()
+      .fold(value, |value, layer| layer.apply(value, time))
+  }
+
+  /// Timing seen by the animation, which starts `delay` seconds later than
+  /// the clip
+  fn delayed(&self, mut time: Time) -> Time {
+    if self.delay > 0.0 {
+      let fps = match &self.source {
+        Source::Keyframes(track) => track.fps,
+        Source::Function(_) => time.fps,
+      };
+      time.clip_frame = time.clip_frame.saturating_sub(self.delay.into_frame(fps));
+      time.clip_time = (time.clip_time - self.delay).max(0.0);
+    }
+
+    time
+  }
+}
+
+impl<T: Interpolate + Clone + Additive + Default + 'static> Animated<T> {
+  /// Stacks `blend` on top of the animation, e.g. a bounce added to a
+  /// keyframed position. Layers are applied in the order they are added,
+  /// `weight` controls how much of each layer is applied (`0.0..=1.0`) and can
+  /// be animated to fade layers in and out. Layers need [`Additive`] values.
+  ///
+  /// ```
+  /// # use vide::prelude::*;
+  /// let bounce = Animated::from_fn(|time: Time| (0.0, (time.clip_time * 8.0).sin().abs() * 40.0));
+  /// let position = Animated::builder()
+  ///   .keyframe(Abs(0.0), ease::LINEAR, (-500.0, 0.0))
+  ///   .keyframe(Abs(2.0), ease::IN_OUT_CUBIC, (500.0, 0.0))
+  ///   .build()
+  ///   .layer(Blend::Add(bounce), 0.5);
+  /// ```
+  pub fn layer(mut self, blend: Blend<T>, weight: impl Into<Animated<f64>>) -> Self {
+    self.layers.push(AnimationLayer {
+      blend,
+      weight: weight.into(),
+      zero: T::default(),
+      add: T::add,
+    });
+    self
+  }
+
+  /// Shakes the value with [`Noise`] seeded by `seed`, moving it by up to
+  /// `amplitude` about `frequency` times per second, like After Effects'
+  /// `wiggle()`. The same seed always shakes the same way.
+  ///
+  /// All components move together along `amplitude`, layer wiggles with
+  /// different seeds to shake them separately:
+  ///
+  /// ```
+  /// # use vide::prelude::*;
+  /// let shake = Animated::from((0.0f32, 0.0f32))
+  ///   .wiggle(1, 4.0, (12.0, 0.0))
+  ///   .wiggle(2, 4.0, (0.0, 12.0));
+  /// ```
+  pub fn wiggle(self, seed: u64, frequency: f64, amplitude: impl Into<T>) -> Self {
+    self.wiggle_with(Noise::new(seed), frequency, amplitude)
+  }
+
+  /// [`Animated::wiggle`] driven by `noise`, e.g. to use another
+  /// [`NoiseKind`](super::noise::NoiseKind)
+  pub fn wiggle_with(self, noise: Noise, frequency: f64, amplitude: impl Into<T>) -> Self {
+    let amplitude = amplitude.into();
+    let wiggle = Self::from_fn(move |time| {
+      let strength = noise.sample(time.clip_time * frequency);
+      T::interpolate(T::default(), amplitude.clone(), strength)
+    });
+
+    self.layer(Blend::Add(wiggle), 1.0)
+  }
+}
+
+/// How a layer is combined with the value below it, see [`Animated::layer`]
+#[derive(Clone)]
+pub enum Blend<T: Interpolate + Clone> {
+  /// Replaces the value, a weight below `1.0` blends between the value and
+  /// the layer
+  Override(Animated<T>),
+  /// Adds the layer to the value, like a bounce or shake on top of a motion
+  Add(Animated<T>),
+  /// Scales the value by a factor, `1.0` leaves it unchanged
+  Multiply(Animated<f64>),
+}
+
+#[derive(Clone)]
+struct AnimationLayer<T: Interpolate + Clone> {
+  blend: Blend<T>,
+  weight: Animated<f64>,
+  /// `T::default()`, what adding or scaling is relative to
+  zero: T,
+  /// [`Additive::add`], layers can only be added to values that are
+  /// [`Additive`]
+  add: fn(T, T) -> T,
+}
+
+impl<T: Interpolate + Clone> AnimationLayer<T> {
+  fn apply(&self, value: T, time: Time) -> T {
+    let weight = self.weight.evaluate(time);
+    match &self.blend {
+      Blend::Override(layer) => T::interpolate(value, layer.evaluate(time), weight),
+      Blend::Add(layer) => {
+        let added = T::interpolate(self.zero.clone(), layer.evaluate(time), weight);
+        (self.add)(value, added)
+      }
+      Blend::Multiply(factor) => {
+        let factor = lerp!(1.0, factor.evaluate(time), weight);
+        T::interpolate(self.zero.clone(), value, factor)
+      }
+    }
+  }
+}
+
+impl<T: Interpolate + Clone> Timed for Animated<T> {
+  /// Keyframed animations end at their last keyframe (after repeating),
+  /// functions and endless repeats never end
+  fn span(&self) -> Range<f64> {
+    let end = match &self.source {
+      Source::Keyframes(track) => {
+        let last = track.keyframes.last().map_or(0, |k| k.frame) as f64 / track.fps;
+        match track.repeat {
+          Repeat::Hold => last,
+          Repeat::Loop(times) | Repeat::PingPong(times) => last * times.max(1) as f64,
+          Repeat::LoopForever | Repeat::PingPongForever | Repeat::Extrapolate => f64::INFINITY,
+        }
+      }
+      Source::Function(_) => f64::INFINITY,
+    };
+
+    self.delay..self.delay + end
+  }
+
+  fn delay(mut self, seconds: f64) -> Self {
+    self.delay = (self.delay + seconds).max(0.0);
+    self
+  }
+}
+
+impl<T> Default for Animated<T>
+where
+  T: Default + Interpolate + Clone,
+{
+  fn default() -> Self {
+    Self {
+      source: Source::Keyframes(Track {
+        initial: T::default(),
+        keyframes: Vec::new(),
+        fps: 60.0,
+        repeat: Repeat::Hold,
+        offset: None,
+        cursor: Cell::new(0),
+      }),
+      layers: vec![],
+      delay: 0.0,
+    }
+  }
+}
+
+#[derive(Clone)]
+struct Track<T: Interpolate + Clone> {
+  initial: T,
+  /// Sorted by frame
+  keyframes: Vec<Keyframe<T>>,
+  fps: f64,
+  repeat: Repeat,
+  /// [`offset`], lets interrupted springs keep their momentum. Only available
+  /// for [`Additive`] values, set by [`AnimatedBuilder::spring`]
+  offset: Option<fn(T, T, T) -> T>,
+  /// Index of the keyframe evaluated last, frames are usually evaluated in
+  /// order so the next lookup most likely starts here
+  cursor: Cell<usize>,
+}
+
+impl<T: Interpolate + Clone> Track<T> {
+  fn evaluate(&self, frame: u64) -> T {
+    let end = self.keyframes.last().map(|k| k.frame).unwrap_or(0);
+    if end == 0 || frame < end {
+      return self.evaluate_frame(frame);
+    }
+
+    match self.repeat {
+      // A hold jumps on its last frame, that jump is not a velocity to keep
+      Repeat::Extrapolate if self.holds_at_end() => self.evaluate_frame(end),
+      // Continues the motion of the last frame, interpolating past the end
+      Repeat::Extrapolate => T::interpolate(
+        self.evaluate_frame(end - 1),
+        self.evaluate_frame(end),
+        (frame - end + 1) as f64,
+      ),
+      repeat => self.evaluate_frame(repeat.frame(frame, end)),
+    }
+  }
+
+  /// Whether the last keyframe is reached by a hold, the value stands still
+  /// right before it
+  fn holds_at_end(&self) -> bool {
+    self
+      .keyframes
+      .last()
+      .is_some_and(|last| matches!(last.interpolation, Interpolation::Hold))
+  }
+
+  fn evaluate_frame(&self, frame: u64) -> T {
+    // Fallback when no keyframes
+    let Some(last) = self.keyframes.last() else {
+      return self.initial.clone();
+    };
+
+    // Interpolate towards the first keyframe that has not passed yet
+    let index = self.find(frame);
+    if index < self.keyframes.len() {
+      return self.evaluate_segment(index, frame);
+    }
+
+    // When all keyframes have passed, a spring keeps moving until it settles
+    if last.spring().is_some() {
+      self.evaluate_springs(self.keyframes.len() - 1, frame)
+    } else {
+      last.state.clone()
+    }
+  }
+
+  /// Index of the first keyframe at or after `frame`, or the number of
+  /// keyframes when all of them have passed
+  fn find(&self, frame: u64) -> usize {
+    let is_segment = |index: usize| {
+      index <= self.keyframes.len()
+        && self.keyframes.get(index).is_none_or(|k| k.frame >= frame)
+        && (index == 0 || self.keyframes[index - 1].frame < frame)
+    };
+
+    let cursor = self.cursor.get();
+    let index = if is_segment(cursor) {
+      cursor
+    } else if is_segment(cursor + 1) {
+      cursor + 1
+    } else {
+      self.keyframes.partition_point(|k| k.frame < frame)
+    };
+
+    self.cursor.set(index);
+    index
+  }
+
+  /// Evaluates the motion towards the keyframe at `index`
+  fn evaluate_segment(&self, index: usize, frame: u64) -> T {
+    let keyframe = &self.keyframes[index];
+    if keyframe.spring().is_some() {
+      return self.evaluate_springs(index, frame);
+    }
+
+    // When on first keyframe, interpolate with self.initial
+    let previous = match index.checked_sub(1) {
+      Some(previous) => self.keyframes[previous].frame,
+      None => 0,
+    };
+
+    keyframe.evaluate(
+      Keyframe {
+        interpolation: Interpolation::Linear,
+        mix: Mix::default(),
+        roving: false,
+        state: self.segment_start(index),
+        frame: previous,
+      },
+      frame,
+    )
+  }
+
+  /// Value the motion towards the keyframe at `index` starts from. Springs
+  /// might not have settled yet when the next keyframe starts, in which case
+  /// it starts from wherever the spring is at that point
+  fn segment_start(&self, index: usize) -> T {
+    match index.checked_sub(1) {
+      None => self.initial.clone(),
+      Some(previous) if self.keyframes[previous].spring().is_some() => {
+        self.evaluate_springs(previous, self.keyframes[previous].frame)
+      }
+      Some(previous) => self.keyframes[previous].state.clone(),
+    }
+  }
+
+  /// Evaluates consecutive spring keyframes up to the one at `index`.
+  ///
+  /// Springs are linear, so a spring interrupted by a new target keeps its
+  /// momentum by adding the motion towards the new target on top of the
+  /// motion that has not settled yet. Without [`Additive`] values, every
+  /// spring starts from wherever the previous one was when it got interrupted
+  fn evaluate_springs(&self, index: usize, frame: u64) -> T {
+    let Some(offset) = self.offset else {
+      let keyframe = &self.keyframes[index];
+      let progress = self.spring_progress(index, frame);
+      return keyframe
+        .mix
+        .apply(self.segment_start(index), keyframe.state.clone(), progress);
+    };
+
+    let mut first = index;
+    while first > 0 && self.keyframes[first - 1].spring().is_some() {
+      first -= 1;
+    }
+
+    let mut value = self.segment_start(first);
+    let mut target = value.clone();
+    for index in first..=index {
+      let keyframe = &self.keyframes[index];
+      let progress = self.spring_progress(index, frame);
+
+      // Where a fresh spring from the previous target would be by now
+      let moved = keyframe
+        .mix
+        .apply(target.clone(), keyframe.state.clone(), progress);
+      value = offset(value, target, moved);
+      target = keyframe.state.clone();
+    }
+
+    value
+  }
+
+  /// Progress of the spring towards the keyframe at `index`, which is
+  /// released at the previous keyframe
+  fn spring_progress(&self, index: usize, frame: u64) -> f64 {
+    let released = match index.checked_sub(1) {
+      Some(previous) => self.keyframes[previous].frame,
+      None => 0,
+    };
+    let seconds = frame.saturating_sub(released) as f64 / self.fps;
+    self.keyframes[index]
+      .spring()
+      .unwrap_or_default()
+      .progress(seconds)
   }
 }
 
@@ -233,6 +1502,12 @@ pub struct AnimatedBuilder<T: Interpolate + Clone> {
   initial: Option<T>,
   keyframes: Vec<Keyframe<T>>,
   fps: f64,
+  repeat: Repeat,
+  mix: Mix<T>,
+  /// Carries over the momentum of springs, set by [`AnimatedBuilder::spring`]
+  offset: Option<fn(T, T, T) -> T>,
+  /// Measures roving keyframes, set by [`AnimatedBuilder::rove`]
+  distance: Option<fn(&T, &T) -> f64>,
 }
 
 impl<T: Interpolate + Clone> Default for AnimatedBuilder<T> {
@@ -241,6 +1516,10 @@ impl<T: Interpolate + Clone> Default for AnimatedBuilder<T> {
       initial: None,
       keyframes: vec![],
       fps: 60.0,
+      repeat: Repeat::Hold,
+      mix: Mix::default(),
+      offset: None,
+      distance: None,
     }
   }
 }
@@ -249,7 +1528,24 @@ impl<T: Interpolate + Clone> AnimatedBuilder<T> {
   pub fn keyframe(
     &mut self,
     at: KeyframeTiming<impl IntoFrame>,
-    easing: EasingFunction,
+    easing: impl Into<Easing>,
+    state: impl Into<T>,
+  ) -> &mut Self {
+    self.keyframe_with(at, Interpolation::Eased(easing.into()), state)
+  }
+
+  /// Keeps the previous value until `at`, then jumps to `state`
+  pub fn step(&mut self, at: KeyframeTiming<impl IntoFrame>, state: impl Into<T>) -> &mut Self {
+    self.keyframe_with(at, Interpolation::Hold, state)
+  }
+
+  /// Adds a keyframe reaching `state` at `at` with any kind of interpolation,
+  /// e.g. [`Interpolation::Bezier`] for curved motion. A keyframe at frame `0`
+  /// sets the initial value
+  pub fn keyframe_with(
+    &mut self,
+    at: KeyframeTiming<impl IntoFrame>,
+    interpolation: Interpolation<T>,
     state: impl Into<T>,
   ) -> &mut Self {
     let frame = match at {
@@ -265,14 +1561,17 @@ impl<T: Interpolate + Clone> AnimatedBuilder<T> {
     } else {
       self.push_keyframe(Keyframe {
         frame,
-        easing,
+        interpolation,
+        mix: self.mix.clone(),
+        roving: false,
         state: state.into(),
       })
     }
   }
 
+  /// Adds a keyframe, keyframes don't have to be added in order
   pub fn push_keyframe(&mut self, keyframe: Keyframe<T>) -> &mut Self {
-    self.keyframes.push(keyframe);
+    insert_keyframe(&mut self.keyframes, keyframe);
     self
   }
 
@@ -282,13 +1581,21 @@ impl<T: Interpolate + Clone> AnimatedBuilder<T> {
     let keyframe = if let Some(last) = self.keyframes.last().cloned() {
       Keyframe {
         state: last.state.clone(),
-        easing: LINEAR,
+        // Lets a spring keep settling instead of interrupting it
+        interpolation: match last.spring() {
+          Some(spring) => Interpolation::Spring(spring),
+          None => Interpolation::Hold,
+        },
+        mix: self.mix.clone(),
+        roving: false,
         frame: last.frame + frame,
       }
     } else {
       Keyframe {
         state: initial.clone(),
-        easing: LINEAR,
+        interpolation: Interpolation::Hold,
+        mix: self.mix.clone(),
+        roving: false,
         frame,
       }
     };
@@ -296,10 +1603,611 @@ impl<T: Interpolate + Clone> AnimatedBuilder<T> {
     self.push_keyframe(keyframe)
   }
 
+  /// Interpolates keyframes added after this call with `mix` instead of
+  /// [`Interpolate::interpolate`], e.g. to animate colors in a different color
+  /// space (see [`AnimatedBuilder::color_space`])
+  pub fn mix(&mut self, mix: impl Into<Mix<T>>) -> &mut Self {
+    self.mix = mix.into();
+    self
+  }
+
+  /// Sets what happens after the last keyframe, e.g. [`Repeat::LoopForever`]
+  /// for a spinner. The keyframes repeat starting at frame `0`
+  pub fn repeat(&mut self, repeat: Repeat) -> &mut Self {
+    self.repeat = repeat;
+    self
+  }
+
   pub fn build(&self) -> Animated<T> {
+    let initial = self.initial.to_owned().unwrap();
+    let mut keyframes = self.keyframes.to_owned();
+    if let Some(distance) = self.distance {
+      rove(&initial, &mut keyframes, distance);
+    }
+
     Animated {
-      initial: self.initial.to_owned().unwrap(),
-      keyframes: self.keyframes.to_owned(),
+      source: Source::Keyframes(Track {
+        initial,
+        keyframes,
+        fps: self.fps,
+        repeat: self.repeat,
+        offset: self.offset,
+        cursor: Cell::new(0),
+      }),
+      layers: vec![],
+      delay: 0.0,
+    }
+  }
+}
+
+impl<T: Interpolate + Clone + Additive> AnimatedBuilder<T> {
+  /// Lets the value spring towards `state`, starting at the previous keyframe.
+  ///
+  /// The spring keeps moving past `at` until it settles, `at` is where the
+  /// next keyframe starts. When that is another spring, the new motion
+  /// carries over the velocity of the unsettled one. Any other keyframe
+  /// starts wherever the spring is at that point
+  pub fn spring(
+    &mut self,
+    at: KeyframeTiming<impl IntoFrame>,
+    spring: Spring,
+    state: impl Into<T>,
+  ) -> &mut Self {
+    self.offset = Some(offset);
+    self.keyframe_with(at, Interpolation::Spring(spring), state)
+  }
+}
+
+impl<T: Interpolate + Clone + Distance> AnimatedBuilder<T> {
+  /// Makes the last keyframe roving: instead of its own time, it is reached
+  /// whenever a constant speed between the surrounding keyframes that aren't
+  /// roving gets there, like in After Effects. Smooths out motion along many
+  /// points that were keyframed at arbitrary times.
+  ///
+  /// Keyframes around roving ones should be [`Interpolation::Linear`] (or
+  /// [`ease::LINEAR`]) for a truly constant speed. The last keyframe can't
+  /// rove, building panics when no keyframe after a roving one stays put
+  pub fn rove(&mut self) -> &mut Self {
+    if let Some(last) = self.keyframes.last_mut() {
+      last.roving = true;
+    }
+    self.distance = Some(T::distance);
+    self
+  }
+}
+
+/// Retimes runs of roving keyframes, spreading them between the keyframes
+/// around them by how far the value travels. Retimed keyframes stay on their
+/// own frames as long as the run has enough of them
+fn rove<T: Interpolate + Clone>(
+  initial: &T,
+  keyframes: &mut [Keyframe<T>],
+  distance: fn(&T, &T) -> f64,
+) {
+  // Samples of the curve used to measure bezier keyframes
+  const BEZIER_SAMPLES: usize = 16;
+
+  let length = |keyframes: &[Keyframe<T>], index: usize| {
+    let keyframe = &keyframes[index];
+    let start = match index.checked_sub(1) {
+      Some(previous) => keyframes[previous].state.clone(),
+      None => initial.clone(),
+    };
+
+    match &keyframe.interpolation {
+      Interpolation::Bezier {
+        out_tangent,
+        in_tangent,
+        ..
+      } => {
+        let mut length = 0.0;
+        let mut point = start.clone();
+        for i in 1..=BEZIER_SAMPLES {
+          let t = i as f64 / BEZIER_SAMPLES as f64;
+          let next = keyframe.bezier(start.clone(), out_tangent, in_tangent, t);
+          length += distance(&point, &next);
+          point = next;
+        }
+        length
+      }
+      _ => distance(&start, &keyframe.state),
+    }
+  };
+
+  let mut index = 0;
+  while index < keyframes.len() {
+    if !keyframes[index].roving {
+      index += 1;
+      continue;
+    }
+
+    let end = (index..keyframes.len())
+      .find(|&i| !keyframes[i].roving)
+      .expect("roving keyframes need a later keyframe that doesn't rove");
+
+    let start_frame = index.checked_sub(1).map_or(0, |i| keyframes[i].frame);
+    let duration = keyframes[end].frame.saturating_sub(start_frame) as f64;
+    let lengths: Vec<f64> = (index..=end).map(|i| length(keyframes, i)).collect();
+    let total: f64 = lengths.iter().sum();
+
+    if total > 0.0 {
+      let mut travelled = 0.0;
+      let mut previous = start_frame;
+      for (i, length) in (index..end).zip(&lengths) {
+        travelled += length;
+        let frame = start_frame + (duration * travelled / total).round() as u64;
+        // Leaves a frame for every keyframe left before the end
+        let latest = keyframes[end].frame.saturating_sub((end - i) as u64);
+        previous = frame.max(previous + 1).min(latest).max(previous);
+        keyframes[i].frame = previous;
+      }
+    }
+
+    index = end + 1;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{KeyframeTiming::*, *};
+
+  #[test]
+  fn add_layers_add_exactly() {
+    let value = Animated::from(10i32).layer(Blend::Add(Animated::from(1i32)), 1.0);
+    assert_eq!(value.evaluate(Time::default()), 11);
+
+    let value = Animated::from(3u8).layer(Blend::Add(Animated::from(5u8)), 1.0);
+    assert_eq!(value.evaluate(Time::default()), 8);
+
+    let value = Animated::from((1.0, -2.0)).layer(Blend::Add(Animated::from((0.5, 0.5))), 0.5);
+    assert_eq!(value.evaluate(Time::default()), (1.25, -1.75));
+  }
+
+  #[test]
+  fn layers_are_applied_in_order() {
+    let value = Animated::from(2.0)
+      .layer(Blend::Add(Animated::from(1.0)), 1.0)
+      .layer(Blend::Multiply(Animated::from(2.0)), 1.0)
+      .layer(Blend::Override(Animated::from(10.0)), 0.5);
+    assert_eq!(value.evaluate(Time::default()), 8.0);
+  }
+
+  fn assert_close(actual: f64, expected: f64) {
+    assert!(
+      (actual - expected).abs() < 1e-6,
+      "expected {expected}, got {actual}"
+    );
+  }
+
+  #[test]
+  fn springs_settle_at_the_target() {
+    let springs = [
+      Spring::default(),
+      // Critically damped and overdamped
+      Spring::new(100.0, 20.0, 1.0),
+      Spring::new(100.0, 40.0, 1.0),
+      Spring::new(100.0, 10.0, 1.0).with_velocity(5.0),
+    ];
+
+    for spring in springs {
+      assert_eq!(spring.progress(0.0), 0.0);
+      assert!((spring.progress(10.0) - 1.0).abs() < 1e-6, "{spring:?}");
+    }
+  }
+
+  #[test]
+  fn only_underdamped_springs_overshoot() {
+    let peak = |spring: Spring| {
+      (0..120)
+        .map(|frame| spring.progress(frame as f64 / 60.0))
+        .fold(f64::MIN, f64::max)
+    };
+
+    assert!(peak(Spring::default()) > 1.0);
+    assert!(peak(Spring::new(100.0, 20.0, 1.0)) <= 1.0);
+    assert!(peak(Spring::new(100.0, 40.0, 1.0)) <= 1.0);
+  }
+
+  #[test]
+  #[should_panic(expected = "mass has to be positive")]
+  fn springs_need_mass() {
+    Spring::new(100.0, 10.0, 0.0);
+  }
+
+  #[test]
+  fn interrupted_springs_keep_their_velocity() {
+    // Critically damped, so the first motion is still heading towards its
+    // target when it gets interrupted
+    let spring = Spring::new(100.0, 20.0, 1.0);
+    let animation = Animated::builder()
+      .keyframe(Abs(0), ease::LINEAR, 0.0)
+      .spring(Abs(30), spring, 100.0)
+      .spring(Abs(60), spring, 200.0)
+      .build();
+    let value = |frame| animation.evaluate(Time::new(frame, 120, 60.0));
+
+    // Both motions add up, the second one starting when the first keyframe is
+    // reached
+    for frame in [15, 30, 45, 90] {
+      let first = spring.progress(frame as f64 / 60.0);
+      let second = spring.progress((frame as f64 - 30.0).max(0.0) / 60.0);
+      assert_close(value(frame), 100.0 * first + 100.0 * second);
+    }
+
+    // A spring starting at rest would barely move in the first frame
+    let restarted = 100.0 * spring.progress(1.0 / 60.0);
+    assert!(value(31) - value(30) > restarted);
+  }
+
+  #[test]
+  fn interrupted_integer_springs_are_exact() {
+    let spring = Spring::new(100.0, 20.0, 1.0);
+    let animation = Animated::<i32>::builder()
+      .keyframe(Abs(0), ease::LINEAR, 0)
+      .spring(Abs(30), spring, 101)
+      .spring(Abs(60), spring, 200)
+      .build();
+
+    assert_eq!(animation.evaluate(Time::new(6000, 6000, 60.0)), 200);
+  }
+
+  fn keyframe(frame: u64, state: f64) -> Keyframe<f64> {
+    Keyframe {
+      interpolation: Interpolation::Linear,
+      mix: Mix::default(),
+      roving: false,
+      state,
+      frame,
+    }
+  }
+
+  #[test]
+  fn keyframes_stay_sorted() {
+    let mut keyframes = vec![];
+    for (frame, state) in [(30, 1.0), (10, 2.0), (20, 3.0), (10, 4.0)] {
+      insert_keyframe(&mut keyframes, keyframe(frame, state));
     }
+
+    let order: Vec<_> = keyframes.iter().map(|k| (k.frame, k.state)).collect();
+    // Keyframes on the same frame keep the order they were added in
+    assert_eq!(order, [(10, 2.0), (10, 4.0), (20, 3.0), (30, 1.0)]);
+  }
+
+  #[test]
+  fn keyframes_added_out_of_order_are_evaluated_in_order() {
+    let animation = Animated::builder()
+      .keyframe(Abs(0), ease::LINEAR, 0.0)
+      .keyframe(Abs(20), ease::LINEAR, 20.0)
+      .keyframe(Abs(10), ease::LINEAR, 10.0)
+      .build();
+
+    for frame in [0, 5, 10, 15, 20] {
+      assert_close(animation.evaluate(Time::new(frame, 20, 60.0)), frame as f64);
+    }
+  }
+
+  #[test]
+  fn keyframes_on_the_same_frame_jump() {
+    // Used to divide by zero
+    assert_eq!(keyframe(10, 1.0).evaluate(keyframe(10, 0.0), 10), 1.0);
+
+    let animation = Animated::builder()
+      .keyframe(Abs(0), ease::LINEAR, 0.0)
+      .keyframe(Abs(10), ease::LINEAR, 1.0)
+      .keyframe(Abs(10), ease::LINEAR, 2.0)
+      .keyframe(Abs(20), ease::LINEAR, 3.0)
+      .build();
+    assert_close(animation.evaluate(Time::new(10, 20, 60.0)), 1.0);
+    assert_close(animation.evaluate(Time::new(15, 20, 60.0)), 2.5);
+  }
+
+  #[test]
+  fn frames_before_the_previous_keyframe_dont_underflow() {
+    assert_eq!(keyframe(20, 1.0).evaluate(keyframe(10, 0.0), 5), 0.0);
+    // Keyframes in the wrong order jump straight to the later one
+    assert_eq!(keyframe(10, 1.0).evaluate(keyframe(20, 0.0), 15), 1.0);
+  }
+
+  #[test]
+  fn lookups_going_backwards_find_the_right_keyframe() {
+    let animation = Animated::builder()
+      .keyframe(Abs(0), ease::LINEAR, 0.0)
+      .keyframe(Abs(10), ease::LINEAR, 10.0)
+      .keyframe(Abs(20), ease::LINEAR, 20.0)
+      .keyframe(Abs(30), ease::LINEAR, 30.0)
+      .build();
+
+    // Forwards, backwards past several keyframes, then one keyframe back, like
+    // scrubbing in the preview
+    for frame in [0, 5, 12, 25, 30, 35, 3, 28, 18, 9, 10, 11] {
+      let expected = (frame as f64).min(30.0);
+      assert_close(animation.evaluate(Time::new(frame, 40, 60.0)), expected);
+    }
+  }
+
+  #[test]
+  fn repeats_play_every_pass_the_same() {
+    let cases = [
+      (Repeat::Hold, [0.0, 5.0, 10.0, 10.0, 10.0, 10.0, 10.0]),
+      (Repeat::Loop(2), [0.0, 5.0, 0.0, 5.0, 10.0, 10.0, 10.0]),
+      (Repeat::LoopForever, [0.0, 5.0, 0.0, 5.0, 0.0, 5.0, 0.0]),
+      (Repeat::PingPong(2), [0.0, 5.0, 10.0, 5.0, 0.0, 0.0, 0.0]),
+      (Repeat::PingPong(3), [0.0, 5.0, 10.0, 5.0, 0.0, 5.0, 10.0]),
+      (
+        Repeat::PingPongForever,
+        [0.0, 5.0, 10.0, 5.0, 0.0, 5.0, 10.0],
+      ),
+      (
+        Repeat::Extrapolate,
+        [0.0, 5.0, 10.0, 15.0, 20.0, 25.0, 30.0],
+      ),
+    ];
+
+    for (repeat, expected) in cases {
+      let animation = Animated::<f64>::builder()
+        .keyframe(Abs(0), ease::LINEAR, 0.0)
+        .keyframe(Abs(10), ease::LINEAR, 10.0)
+        .repeat(repeat)
+        .build();
+
+      for (frame, expected) in [0, 5, 10, 15, 20, 25, 30].into_iter().zip(expected) {
+        let value = animation.evaluate(Time::new(frame, 30, 60.0));
+        assert!(
+          (value - expected).abs() < 1e-6,
+          "{repeat:?} at {frame}: expected {expected}, got {value}"
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn loops_end_on_the_last_keyframe() {
+    let animation = Animated::builder()
+      .keyframe(Abs(0), ease::LINEAR, 0.0)
+      .keyframe(Abs(10), ease::LINEAR, 10.0)
+      .repeat(Repeat::Loop(3))
+      .build();
+    let value = |frame| animation.evaluate(Time::new(frame, 40, 60.0));
+
+    assert_close(value(29), 9.0);
+    assert_close(value(30), 10.0);
+    assert_eq!(animation.span(), 0.0..0.5);
+  }
+
+  #[test]
+  fn mixes_can_capture_state() {
+    let step = 4.0;
+    let animation = Animated::builder()
+      .mix(move |a: f64, b, t| (f64::interpolate(a, b, t) / step).floor() * step)
+      .keyframe(Abs(0), ease::LINEAR, 0.0)
+      .keyframe(Abs(10), ease::LINEAR, 10.0)
+      .build();
+    let value = |frame| animation.evaluate(Time::new(frame, 10, 60.0));
+
+    assert_close(value(3), 0.0);
+    assert_close(value(5), 4.0);
+    assert_close(value(9), 8.0);
+  }
+
+  #[test]
+  fn functions_are_delayed_at_the_video_fps() {
+    let animation = Animated::from_fn(|time: Time| time.clip_frame as f64).delay(1.0);
+
+    assert_close(animation.evaluate(Time::new(40, 100, 30.0)), 10.0);
+    assert_close(animation.evaluate(Time::new(130, 200, 120.0)), 10.0);
+  }
+
+  #[test]
+  fn extrapolation_after_a_hold_stands_still() {
+    let animation = Animated::<f64>::builder()
+      .keyframe(Abs(0), ease::LINEAR, 0.0)
+      .keyframe(Abs(10), ease::LINEAR, 10.0)
+      .step(Abs(20), 50.0)
+      .repeat(Repeat::Extrapolate)
+      .build();
+
+    for frame in [20, 21, 30, 100] {
+      assert_eq!(animation.evaluate(Time::new(frame, 100, 60.0)), 50.0);
+    }
+  }
+
+  #[test]
+  fn holds_jump_once_reached() {
+    let animation = Animated::builder()
+      .keyframe(Abs(0), ease::LINEAR, 0.0)
+      .step(Abs(10), 5.0)
+      .build();
+    let value = |frame| animation.evaluate(Time::new(frame, 20, 60.0));
+
+    assert_close(value(0), 0.0);
+    assert_close(value(9), 0.0);
+    assert_close(value(10), 5.0);
+    assert_close(value(20), 5.0);
+  }
+
+  #[test]
+  fn beziers_follow_their_tangents() {
+    let animation = Animated::builder()
+      .keyframe(Abs(0), ease::LINEAR, (0.0, 0.0))
+      .keyframe_with(
+        Abs(10),
+        Interpolation::Bezier {
+          out_tangent: (0.0, 10.0),
+          in_tangent: (10.0, 10.0),
+          easing: ease::LINEAR.into(),
+        },
+        (10.0, 0.0),
+      )
+      .build();
+    let value = |frame| animation.evaluate(Time::new(frame, 10, 60.0));
+
+    assert_eq!(value(0), (0.0, 0.0));
+    assert_eq!(value(5), (5.0, 7.5));
+    assert_eq!(value(10), (10.0, 0.0));
+  }
+
+  #[test]
+  fn roving_keyframes_move_at_a_constant_speed() {
+    let animation = Animated::builder()
+      .keyframe(Abs(0), ease::LINEAR, 0.0)
+      .keyframe(Abs(2), ease::LINEAR, 10.0)
+      .rove()
+      .keyframe(Abs(3), ease::LINEAR, 40.0)
+      .rove()
+      .keyframe(Abs(20), ease::LINEAR, 50.0)
+      .build();
+    let Source::Keyframes(track) = &animation.source else {
+      unreachable!()
+    };
+
+    let frames: Vec<u64> = track.keyframes.iter().map(|k| k.frame).collect();
+    assert_eq!(frames, [4, 16, 20]);
+    for frame in 0..=20 {
+      assert_close(
+        animation.evaluate(Time::new(frame, 20, 60.0)),
+        frame as f64 * 2.5,
+      );
+    }
+  }
+
+  #[test]
+  fn roving_keyframes_keep_their_own_frames() {
+    let animation = Animated::<f64>::builder()
+      .keyframe(Abs(0), ease::LINEAR, 0.0)
+      .keyframe(Abs(1), ease::LINEAR, 0.1)
+      .rove()
+      .keyframe(Abs(2), ease::LINEAR, 0.2)
+      .rove()
+      .keyframe(Abs(3), ease::LINEAR, 100.0)
+      .build();
+    let Source::Keyframes(track) = &animation.source else {
+      unreachable!()
+    };
+
+    let frames: Vec<u64> = track.keyframes.iter().map(|k| k.frame).collect();
+    assert_eq!(frames, [1, 2, 3]);
+  }
+
+  #[test]
+  #[should_panic(expected = "roving keyframes need a later keyframe")]
+  fn roving_needs_an_end() {
+    Animated::<f64>::builder()
+      .keyframe(Abs(0), ease::LINEAR, 0.0)
+      .keyframe(Abs(10), ease::LINEAR, 10.0)
+      .rove()
+      .build();
+  }
+
+  #[test]
+  fn wiggles_depend_on_their_seed() {
+    let wiggle = |seed| Animated::from(0.0).wiggle(seed, 4.0, 10.0);
+    let values = |animation: Animated<f64>| -> Vec<f64> {
+      (0..60)
+        .map(|frame| animation.evaluate(Time::new(frame, 60, 60.0)))
+        .collect()
+    };
+
+    assert_eq!(values(wiggle(1)), values(wiggle(1)));
+    assert_ne!(values(wiggle(1)), values(wiggle(2)));
+    assert!(values(wiggle(1)).iter().all(|value| value.abs() <= 10.0));
+  }
+
+  #[test]
+  fn arrays_and_vecs_interpolate_element_wise() {
+    assert_eq!(
+      <[f64; 3]>::interpolate([0.0, 10.0, -4.0], [1.0, 20.0, 4.0], 0.5),
+      [0.5, 15.0, 0.0]
+    );
+    assert_eq!(
+      Vec::interpolate(vec![0.0, 2.0], vec![4.0, 2.0], 0.25),
+      vec![1.0, 2.0]
+    );
+    assert_eq!(Vec::<f64>::interpolate(vec![], vec![], 0.5), vec![]);
+  }
+
+  #[test]
+  #[should_panic(expected = "different lengths")]
+  fn vecs_need_the_same_length() {
+    Vec::interpolate(vec![0.0, 1.0], vec![1.0], 0.5);
+  }
+
+  #[test]
+  fn options_switch_halfway_unless_both_are_some() {
+    assert_eq!(Option::interpolate(Some(0.0), Some(4.0), 0.25), Some(1.0));
+    assert_eq!(Option::interpolate(None, Some(4.0), 0.49), None);
+    assert_eq!(Option::interpolate(None, Some(4.0), 0.5), Some(4.0));
+    assert_eq!(Option::interpolate(Some(4.0), None, 0.3), Some(4.0));
+    assert_eq!(Option::interpolate(Some(4.0), None, 0.7), None);
+  }
+
+  #[test]
+  fn cgmath_types_interpolate_component_wise() {
+    assert_eq!(
+      Vector3::interpolate(
+        Vector3::new(0.0, 1.0, 2.0),
+        Vector3::new(2.0, 3.0, 6.0),
+        0.5
+      ),
+      Vector3::new(1.0, 2.0, 4.0)
+    );
+    assert_eq!(
+      Point2::interpolate(Point2::new(0.0, 0.0), Point2::new(4.0, -4.0), 0.75),
+      Point2::new(3.0, -3.0)
+    );
+  }
+
+  #[test]
+  fn quaternions_rotate_along_the_shortest_arc() {
+    use cgmath::{Deg, InnerSpace, Rad, Rotation3};
+
+    let a = Quaternion::from_angle_z(Deg(0.0));
+    let b = Quaternion::from_angle_z(Deg(90.0));
+    for t in [0.0, 0.1, 0.5, 0.9, 1.0] {
+      let rotation = Quaternion::interpolate(a, b, t);
+      assert_close(rotation.magnitude(), 1.0);
+
+      let expected = Quaternion::from_angle_z(Rad(t * std::f64::consts::FRAC_PI_2));
+      assert_close(rotation.dot(expected).abs(), 1.0);
+    }
+
+    // 350° is only 10° away from 0°, going the short way around
+    let c = Quaternion::from_angle_z(Deg(350.0));
+    let halfway = Quaternion::interpolate(a, c, 0.5);
+    assert_close(halfway.dot(Quaternion::from_angle_z(Deg(-5.0))).abs(), 1.0);
+  }
+
+  #[test]
+  fn functions_see_the_time_they_are_evaluated_at() {
+    let animation =
+      Animated::from_fn(|time: Time| (time.video_frame, time.clip_frame, time.clip_time, time.fps));
+
+    let time = Time::new(90, 200, 30.0).child(30, 150);
+    assert_eq!(animation.evaluate(time), (90, 60, 2.0, 30.0));
+  }
+
+  #[test]
+  fn delayed_functions_only_shift_the_clip_time() {
+    let animation = Animated::from_fn(|time: Time| (time.video_time, time.clip_time)).delay(0.5);
+
+    assert_eq!(animation.evaluate(Time::new(60, 200, 60.0)), (1.0, 0.5));
+    // Nothing plays before the delay is over
+    assert_eq!(animation.evaluate(Time::new(15, 200, 60.0)), (0.25, 0.0));
+  }
+
+  #[test]
+  fn function_layers_see_the_delayed_time_without_repeats() {
+    let clip_time = Animated::from_fn(|time: Time| time.clip_time);
+    let animation = Animated::builder()
+      .keyframe(Abs(0), ease::LINEAR, 0.0)
+      .keyframe(Abs(10), ease::LINEAR, 10.0)
+      .repeat(Repeat::LoopForever)
+      .build()
+      .layer(Blend::Add(clip_time), 1.0)
+      .delay(1.0);
+
+    // 25 frames after the delay: the keyframes loop back to 5, the layer
+    // keeps counting seconds
+    let value = animation.evaluate(Time::new(85, 200, 60.0));
+    assert_close(value, 5.0 + 25.0 / 60.0);
   }
 }
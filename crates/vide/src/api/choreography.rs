@@ -0,0 +1,330 @@
+use std::ops::Range;
+
+use super::animation::ease::{Easing, LINEAR};
+
+/// Something placed in time that can be moved around, like clip builders and
+/// animations. Used by [`Stagger`] and [`Timeline`] to choreograph many of
+/// them at once
+pub trait Timed: Sized {
+  /// When it starts and ends, in seconds. The end is infinite when it never
+  /// ends
+  fn span(&self) -> Range<f64>;
+
+  /// Moves it `seconds` later, keyframes keep their timing relative to it
+  fn delay(self, seconds: f64) -> Self;
+}
+
+/// Clip builders placed at `start..end` of their parent, in seconds. They are
+/// [`Timed`] by moving both
+pub trait Placed {
+  /// When it starts and ends in the parent, see [`Timed::span`]
+  fn placement(&self) -> Range<f64>;
+
+  /// The start and end that are moved by [`Timed::delay`]
+  fn placement_mut(&mut self) -> (&mut f64, &mut f64);
+}
+
+impl<T: Placed> Timed for T {
+  fn span(&self) -> Range<f64> {
+    self.placement()
+  }
+
+  fn delay(mut self, seconds: f64) -> Self {
+    let (start, end) = self.placement_mut();
+    *start += seconds;
+    *end += seconds;
+    self
+  }
+}
+
+/// Delays a list of items more and more, so they start one after another.
+///
+/// ```
+/// # use vide::prelude::*;
+/// let rects = Stagger::new(0.1)
+///   .eased(ease::IN_QUADRATIC)
+///   .apply((0..10).map(|i| {
+///     Rect::builder()
+///       .position((i as f32 * 50.0, 0.0))
+///       .timing(1.0..3.0)
+///   }));
+///
+/// assert_eq!(rects[0].span(), 1.0..3.0);
+/// assert_eq!(rects[9].span(), 1.9..3.9);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Stagger {
+  delay: f64,
+  easing: Easing,
+  origin: StaggerOrigin,
+}
+
+/// Which item of a [`Stagger`] starts first, see [`Stagger::origin`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum StaggerOrigin {
+  /// The first item starts first
+  #[default]
+  Start,
+  /// The last item starts first
+  End,
+  /// The middle item starts first, spreading out to both ends
+  Center,
+}
+
+impl Stagger {
+  /// Starts every item `delay` seconds after the one before it
+  pub fn new(delay: f64) -> Self {
+    Self {
+      delay,
+      easing: LINEAR.into(),
+      origin: StaggerOrigin::Start,
+    }
+  }
+
+  /// Starts with the item at `origin`, items start `delay` seconds after
+  /// their neighbour closer to it
+  pub fn origin(mut self, origin: StaggerOrigin) -> Self {
+    self.origin = origin;
+    self
+  }
+
+  /// Spreads the delays along `easing` instead of evenly, the item starting
+  /// last keeps its delay
+  pub fn eased(mut self, easing: impl Into<Easing>) -> Self {
+    self.easing = easing.into();
+    self
+  }
+
+  /// Delay of the item at `index` out of `count` items
+  pub fn delay_of(&self, index: usize, count: usize) -> f64 {
+    if count < 2 {
+      return 0.0;
+    }
+
+    let last = (count - 1) as f64;
+    let (steps, furthest) = match self.origin {
+      StaggerOrigin::Start => (index as f64, last),
+      StaggerOrigin::End => (last - index as f64, last),
+      StaggerOrigin::Center => ((index as f64 - last / 2.0).abs(), last / 2.0),
+    };
+    (self.easing)(steps / furthest) * self.delay * furthest
+  }
+
+  /// Delays every item by its place in the list
+  pub fn apply<T: Timed>(&self, items: impl IntoIterator<Item = T>) -> Vec<T> {
+    let items: Vec<T> = items.into_iter().collect();
+    let count = items.len();
+    items
+      .into_iter()
+      .enumerate()
+      .map(|(index, item)| item.delay(self.delay_of(index, count)))
+      .collect()
+  }
+}
+
+/// Places items one after another or together, keeping their own timing
+/// relative to where they are placed.
+///
+/// ```
+/// # use vide::prelude::*;
+/// let fade_in = || {
+///   Animated::builder()
+///     .keyframe(Abs(0.0), ease::LINEAR, 0.0)
+///     .keyframe(Abs(0.5), ease::OUT_QUADRATIC, 1.0)
+///     .build()
+/// };
+///
+/// // The first two fade in together, the third one 0.2 seconds after that
+/// let opacities: Vec<Animated<f64>> = Timeline::new()
+///   .then(fade_in())
+///   .with(fade_in())
+///   .after(0.2)
+///   .then(fade_in())
+///   .build();
+///
+/// assert_eq!(opacities[1].span(), 0.0..0.5);
+/// assert_eq!(opacities[2].span(), 0.7..1.2);
+/// ```
+pub struct Timeline<T: Timed> {
+  items: Vec<T>,
+  /// Where the items added last were placed
+  previous: f64,
+  /// When everything added so far has ended
+  end: f64,
+  /// Waiting time before the next item
+  gap: f64,
+}
+
+impl<T: Timed> Default for Timeline<T> {
+  fn default() -> Self {
+    Self {
+      items: vec![],
+      previous: 0.0,
+      end: 0.0,
+      gap: 0.0,
+    }
+  }
+}
+
+impl<T: Timed> Timeline<T> {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Starts `item` once everything added so far has ended. Items that never
+  /// end, like clips without an end, push everything after them out of the
+  /// video, use [`Timeline::with`] for those
+  pub fn then(self, item: T) -> Self {
+    let at = self.end;
+    self.place(at, [item], &Stagger::new(0.0))
+  }
+
+  /// Starts `item` together with the item added last
+  pub fn with(self, item: T) -> Self {
+    let at = self.previous;
+    self.place(at, [item], &Stagger::new(0.0))
+  }
+
+  /// Waits `seconds` before the next item starts, negative values let it
+  /// overlap with what came before
+  pub fn after(mut self, seconds: f64) -> Self {
+    self.gap += seconds;
+    self
+  }
+
+  /// Starts `items` once everything added so far has ended, staggered by
+  /// `stagger`
+  pub fn stagger(self, stagger: &Stagger, items: impl IntoIterator<Item = T>) -> Self {
+    let at = self.end;
+    self.place(at, items, stagger)
+  }
+
+  pub fn build(self) -> Vec<T> {
+    self.items
+  }
+
+  fn place(mut self, at: f64, items: impl IntoIterator<Item = T>, stagger: &Stagger) -> Self {
+    let at = (at + self.gap).max(0.0);
+    for item in stagger.apply(items) {
+      let item = item.delay(at);
+      self.end = self.end.max(item.span().end);
+      self.items.push(item);
+    }
+
+    self.previous = at;
+    self.gap = 0.0;
+    self
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::api::animation::ease;
+
+  /// Placed at `start..end`
+  #[derive(Debug, PartialEq)]
+  struct Item(f64, f64);
+
+  impl Placed for Item {
+    fn placement(&self) -> Range<f64> {
+      self.0..self.1
+    }
+
+    fn placement_mut(&mut self) -> (&mut f64, &mut f64) {
+      (&mut self.0, &mut self.1)
+    }
+  }
+
+  fn delays(stagger: &Stagger, count: usize) -> Vec<f64> {
+    (0..count).map(|i| stagger.delay_of(i, count)).collect()
+  }
+
+  #[test]
+  fn staggers_start_at_their_origin() {
+    let stagger = Stagger::new(0.5);
+    assert_eq!(delays(&stagger, 4), [0.0, 0.5, 1.0, 1.5]);
+
+    let stagger = Stagger::new(0.5).origin(StaggerOrigin::End);
+    assert_eq!(delays(&stagger, 4), [1.5, 1.0, 0.5, 0.0]);
+
+    let stagger = Stagger::new(0.5).origin(StaggerOrigin::Center);
+    assert_eq!(delays(&stagger, 5), [1.0, 0.5, 0.0, 0.5, 1.0]);
+    assert_eq!(delays(&stagger, 4), [0.75, 0.25, 0.25, 0.75]);
+  }
+
+  #[test]
+  fn eased_staggers_keep_the_last_delay() {
+    let stagger = Stagger::new(1.0).eased(ease::IN_QUADRATIC);
+    assert_eq!(delays(&stagger, 3), [0.0, 0.5, 2.0]);
+    assert_eq!(delays(&stagger, 1), [0.0]);
+  }
+
+  #[test]
+  fn stagger_delays_each_item() {
+    let items = Stagger::new(0.25).apply([Item(1.0, 2.0), Item(1.0, 2.0), Item(0.0, 3.0)]);
+    assert_eq!(items, [Item(1.0, 2.0), Item(1.25, 2.25), Item(0.5, 3.5)]);
+  }
+
+  #[test]
+  fn delays_move_both_ends() {
+    let item = Item(1.0, 2.0).delay(0.5);
+    assert_eq!(item.span(), 1.5..2.5);
+
+    let item = Item(1.0, f64::INFINITY).delay(-0.5);
+    assert_eq!(item.span(), 0.5..f64::INFINITY);
+  }
+
+  #[test]
+  fn timelines_place_items_one_after_another() {
+    let items = Timeline::new()
+      .then(Item(0.0, 1.0))
+      .then(Item(0.5, 2.0))
+      .with(Item(0.0, 0.5))
+      .build();
+
+    assert_eq!(items, [Item(0.0, 1.0), Item(1.5, 3.0), Item(1.0, 1.5)]);
+  }
+
+  #[test]
+  fn timelines_wait_for_gaps_and_overlap() {
+    let items = Timeline::new()
+      .then(Item(0.0, 1.0))
+      .after(0.5)
+      .then(Item(0.0, 1.0))
+      .after(-0.25)
+      .then(Item(0.0, 1.0))
+      .after(-10.0)
+      .then(Item(0.0, 1.0))
+      .build();
+
+    assert_eq!(
+      items,
+      [
+        Item(0.0, 1.0),
+        Item(1.5, 2.5),
+        Item(2.25, 3.25),
+        Item(0.0, 1.0)
+      ]
+    );
+  }
+
+  #[test]
+  fn timelines_stagger_after_what_came_before() {
+    let items = Timeline::new()
+      .then(Item(0.0, 2.0))
+      .stagger(&Stagger::new(0.5), [Item(0.0, 1.0), Item(0.0, 1.0)])
+      .then(Item(0.0, 1.0))
+      .build();
+
+    assert_eq!(
+      items,
+      [
+        Item(0.0, 2.0),
+        Item(2.0, 3.0),
+        Item(2.5, 3.5),
+        Item(3.5, 4.5)
+      ]
+    );
+  }
+}
@@ -1,6 +1,18 @@
-use super::animation::Interpolate;
+mod parse;
 
-/// Holds RGBA values converted to SRGB color space
+pub use self::parse::ParseColorError;
+use super::animation::{Additive, AnimatedBuilder, Interpolate};
+
+/// Holds RGBA values in linear light with straight (not premultiplied)
+/// alpha.
+///
+/// Colors are usually written in srgb, e.g. in CSS or design tools, and are
+/// converted to linear light using the exact srgb transfer function when
+/// created with [`Color::new`] or the [`rgb8!`](crate::rgb8) macros. Rendering
+/// happens in linear light with premultiplied alpha, so blending and effects
+/// behave like physical light, and the result is encoded back to srgb when
+/// written to the output. See [`Export`](crate::io::Export) for how exported
+/// frames look.
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub struct Color {
   /// Amount of red in the color
@@ -48,9 +60,9 @@ impl Color {
   pub const TRANSPARENT: Color = Self::TRANSPARENT_WHITE;
   /// Transparent black `(0, 0, 0, 0 / #00000000)`
   pub const TRANSPARENT_BLACK: Color = Color {
-    r: 1.0,
-    g: 1.0,
-    b: 1.0,
+    r: 0.0,
+    g: 0.0,
+    b: 0.0,
     a: 0.0,
   };
   /// Transparent white `(255, 255, 255 / #ffffff00)`
@@ -68,17 +80,222 @@ impl Color {
     a: 1.0,
   };
 
-  /// Create a new color from 4 linear components, it will automatically be
-  /// converted to srgb at runtime
+  /// Create a new color from 4 srgb components ranging from `0.0` to `1.0`,
+  /// they are converted to linear light. Alpha is never converted
   pub fn new(r: f64, g: f64, b: f64, a: f64) -> Self {
-    Self::from_raw(r.powf(2.2), g.powf(2.2), b.powf(2.2), a)
+    Self::from_raw(srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b), a)
   }
 
-  /// Create a new color from 4 srgb components. Only use this if you know what
-  /// you're doing, otherwise use [`Color::new`]
+  /// Create a new color from 4 linear light components. Only use this if you
+  /// know what you're doing, otherwise use [`Color::new`]
   pub const fn from_raw(r: f64, g: f64, b: f64, a: f64) -> Self {
     Self { r, g, b, a }
   }
+
+  /// Create a new color from OKLab coordinates, `l` ranges from `0.0` to
+  /// `1.0`, `a` and `b` roughly from `-0.4` to `0.4`
+  pub fn oklab(l: f64, a: f64, b: f64, alpha: f64) -> Self {
+    let l_ = (l + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+    let m_ = (l - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+    let s_ = (l - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+
+    Self::from_raw(
+      4.0767416621 * l_ - 3.3077115913 * m_ + 0.2309699292 * s_,
+      -1.2684380046 * l_ + 2.6097574011 * m_ - 0.3413193965 * s_,
+      -0.0041960863 * l_ - 0.7034186147 * m_ + 1.7076147010 * s_,
+      alpha,
+    )
+  }
+
+  /// Create a new color from OKLCH coordinates, `l` ranges from `0.0` to
+  /// `1.0`, `c` roughly from `0.0` to `0.4` and the hue `h` is in degrees
+  pub fn oklch(l: f64, c: f64, h: f64, alpha: f64) -> Self {
+    let h = h.to_radians();
+    Self::oklab(l, c * h.cos(), c * h.sin(), alpha)
+  }
+
+  /// Create a new color from HSL, the hue `h` is in degrees, saturation `s`
+  /// and lightness `l` range from `0.0` to `1.0`
+  pub fn hsl(h: f64, s: f64, l: f64, alpha: f64) -> Self {
+    let h = h.rem_euclid(360.0) / 60.0;
+    let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+      0 => (chroma, x, 0.0),
+      1 => (x, chroma, 0.0),
+      2 => (0.0, chroma, x),
+      3 => (0.0, x, chroma),
+      4 => (x, 0.0, chroma),
+      _ => (chroma, 0.0, x),
+    };
+    let m = l - chroma / 2.0;
+
+    Self::new(r + m, g + m, b + m, alpha)
+  }
+
+  /// Components in srgb color space, the inverse of [`Color::new`]
+  pub fn to_srgb(self) -> [f64; 4] {
+    [
+      linear_to_srgb(self.r),
+      linear_to_srgb(self.g),
+      linear_to_srgb(self.b),
+      self.a,
+    ]
+  }
+
+  /// Linear light components with the color multiplied by alpha, the way
+  /// colors are stored while rendering
+  pub fn premultiplied(self) -> [f64; 4] {
+    [self.r * self.a, self.g * self.a, self.b * self.a, self.a]
+  }
+
+  /// `[l, a, b, alpha]` in OKLab color space, the inverse of [`Color::oklab`]
+  pub fn to_oklab(self) -> [f64; 4] {
+    let l = (0.4122214708 * self.r + 0.5363325363 * self.g + 0.0514459929 * self.b).cbrt();
+    let m = (0.2119034982 * self.r + 0.6806995451 * self.g + 0.1073969566 * self.b).cbrt();
+    let s = (0.0883024619 * self.r + 0.2817188376 * self.g + 0.6299787005 * self.b).cbrt();
+
+    [
+      0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+      1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+      0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+      self.a,
+    ]
+  }
+
+  /// `[l, c, h, alpha]` in OKLCH color space with the hue in degrees, the
+  /// inverse of [`Color::oklch`]
+  pub fn to_oklch(self) -> [f64; 4] {
+    let [l, a, b, alpha] = self.to_oklab();
+    [
+      l,
+      a.hypot(b),
+      b.atan2(a).to_degrees().rem_euclid(360.0),
+      alpha,
+    ]
+  }
+
+  /// `[h, s, l, alpha]` in HSL with the hue in degrees, the inverse of
+  /// [`Color::hsl`]
+  pub fn to_hsl(self) -> [f64; 4] {
+    let [r, g, b, alpha] = self.to_srgb();
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let chroma = max - min;
+    let l = (max + min) / 2.0;
+
+    if chroma <= f64::EPSILON {
+      return [0.0, 0.0, l, alpha];
+    }
+
+    let h = if max == r {
+      ((g - b) / chroma).rem_euclid(6.0)
+    } else if max == g {
+      (b - r) / chroma + 2.0
+    } else {
+      (r - g) / chroma + 4.0
+    };
+    let s = chroma / (1.0 - (2.0 * l - 1.0).abs());
+
+    [h * 60.0, s, l, alpha]
+  }
+}
+
+/// Decodes an srgb component to linear light, using the piecewise srgb
+/// transfer function
+pub fn srgb_to_linear(value: f64) -> f64 {
+  if value <= 0.04045 {
+    value / 12.92
+  } else {
+    ((value + 0.055) / 1.055).powf(2.4)
+  }
+}
+
+/// Encodes a linear light component to srgb, the inverse of
+/// [`srgb_to_linear`]
+pub fn linear_to_srgb(value: f64) -> f64 {
+  if value <= 0.0031308 {
+    value * 12.92
+  } else {
+    1.055 * value.powf(1.0 / 2.4) - 0.055
+  }
+}
+
+/// The color space colors are interpolated in when animating them, see
+/// [`AnimatedBuilder::color_space`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSpace {
+  /// Interpolates physical light intensities, matches how colors blend
+  #[default]
+  LinearRgb,
+  /// Interpolates the srgb components, like most design tools and browsers do
+  Srgb,
+  /// Perceptually uniform, keeps midpoints from turning grey or muddy
+  Oklab,
+  /// Like [`ColorSpace::Oklab`], but travels around the hue circle on the
+  /// shortest path, keeping midpoints saturated
+  Oklch,
+  /// Travels around the hue circle on the shortest path
+  Hsl,
+}
+
+impl ColorSpace {
+  /// Interpolates from `a` to `b` in this color space, alpha is always
+  /// interpolated linearly
+  pub fn interpolate(self, a: Color, b: Color, t: f64) -> Color {
+    // Converting back and forth isn't exact, keyframes should be
+    if t == 0.0 {
+      return a;
+    } else if t == 1.0 {
+      return b;
+    }
+
+    let lerp = |a: [f64; 4], b: [f64; 4]| [0, 1, 2, 3].map(|i| f64::interpolate(a[i], b[i], t));
+
+    match self {
+      Self::LinearRgb => Color::interpolate(a, b, t),
+      Self::Srgb => {
+        let [r, g, b, alpha] = lerp(a.to_srgb(), b.to_srgb());
+        Color::new(r, g, b, alpha)
+      }
+      Self::Oklab => {
+        let [l, a, b, alpha] = lerp(a.to_oklab(), b.to_oklab());
+        Color::oklab(l, a, b, alpha)
+      }
+      Self::Oklch => {
+        let ([l1, c1, h1, a1], [l2, c2, h2, a2]) = (a.to_oklch(), b.to_oklch());
+        let [l, c, _, alpha] = lerp([l1, c1, 0.0, a1], [l2, c2, 0.0, a2]);
+        Color::oklch(l, c, interpolate_hue((h1, c1), (h2, c2), t), alpha)
+      }
+      Self::Hsl => {
+        let ([h1, s1, l1, a1], [h2, s2, l2, a2]) = (a.to_hsl(), b.to_hsl());
+        let [s, l, _, alpha] = lerp([s1, l1, 0.0, a1], [s2, l2, 0.0, a2]);
+        Color::hsl(interpolate_hue((h1, s1), (h2, s2), t), s, l, alpha)
+      }
+    }
+  }
+}
+
+/// Interpolates between two `(hue, chroma)` pairs on the shortest path around
+/// the hue circle. The hue of a grey is meaningless, so the other one is used
+fn interpolate_hue((a, a_chroma): (f64, f64), (b, b_chroma): (f64, f64), t: f64) -> f64 {
+  const GREY: f64 = 1e-4;
+  match (a_chroma < GREY, b_chroma < GREY) {
+    (true, false) => b,
+    (false, true) => a,
+    _ => {
+      let delta = (b - a + 540.0).rem_euclid(360.0) - 180.0;
+      (a + delta * t).rem_euclid(360.0)
+    }
+  }
+}
+
+impl AnimatedBuilder<Color> {
+  /// Interpolates keyframes added after this call in `space`, call it before
+  /// adding any keyframes to use `space` for the whole animation
+  pub fn color_space(&mut self, space: ColorSpace) -> &mut Self {
+    self.mix(move |a, b, t| space.interpolate(a, b, t))
+  }
 }
 
 impl Interpolate for Color {
@@ -92,6 +309,17 @@ impl Interpolate for Color {
   }
 }
 
+/// Adds the linear light components, alpha included
+impl Additive for Color {
+  fn add(a: Self, b: Self) -> Self {
+    Self::from_raw(a.r + b.r, a.g + b.g, a.b + b.b, a.a + b.a)
+  }
+
+  fn sub(a: Self, b: Self) -> Self {
+    Self::from_raw(a.r - b.r, a.g - b.g, a.b - b.b, a.a - b.a)
+  }
+}
+
 impl From<Color> for [f32; 4] {
   fn from(col: Color) -> Self {
     [col.r as f32, col.g as f32, col.b as f32, col.a as f32]
@@ -105,7 +333,8 @@ impl From<Color> for [f64; 4] {
 }
 
 /// Use this macro if you have a hex color you would like to use. Use
-/// [`rgba8!(r, g, b, a)`] instead if you color isn't fully opaque.
+/// [`rgba8!(r, g, b, a)`] instead if you color isn't fully opaque. Colors can
+/// also be parsed from strings, e.g. `"#da0037".parse::<Color>()`.
 ///
 /// ## Example
 ///
@@ -113,6 +342,7 @@ impl From<Color> for [f64; 4] {
 /// writing the red, green and blue components as their hex values:
 ///
 /// ```
+/// # use vide::rgb8;
 /// # fn main() {
 /// let the_best_color = rgb8!(0xda, 0x00, 0x37);
 /// # }
@@ -134,6 +364,7 @@ macro_rules! rgb8 {
 /// writing the red, green and blue components as their hex values:
 ///
 /// ```
+/// # use vide::rgba8;
 /// # fn main() {
 /// let the_best_color = rgba8!(0xda, 0x00, 0x37, 0xee);
 /// # }
@@ -150,3 +381,117 @@ macro_rules! rgba8 {
     )
   }};
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn srgb_transfer_functions_invert_each_other() {
+    for i in 0..=255 {
+      let value = i as f64 / 255.0;
+      assert!((linear_to_srgb(srgb_to_linear(value)) - value).abs() < 1e-12);
+      assert!((srgb_to_linear(linear_to_srgb(value)) - value).abs() < 1e-12);
+    }
+
+    // The linear part near black and the mid grey of the power curve
+    assert!((srgb_to_linear(0.04) - 0.04 / 12.92).abs() < 1e-12);
+    assert!((srgb_to_linear(0.5) - 0.214).abs() < 1e-3);
+  }
+
+  const SPACES: [ColorSpace; 5] = [
+    ColorSpace::LinearRgb,
+    ColorSpace::Srgb,
+    ColorSpace::Oklab,
+    ColorSpace::Oklch,
+    ColorSpace::Hsl,
+  ];
+
+  fn assert_close(a: [f64; 4], b: [f64; 4]) {
+    assert!(
+      a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-6),
+      "{a:?} != {b:?}"
+    );
+  }
+
+  fn colors() -> [Color; 5] {
+    [
+      Color::new(0.0, 0.0, 0.0, 1.0),
+      Color::new(1.0, 1.0, 1.0, 1.0),
+      Color::new(0.85, 0.0, 0.22, 0.5),
+      Color::new(0.1, 0.6, 0.3, 1.0),
+      Color::new(0.2, 0.3, 0.9, 0.0),
+    ]
+  }
+
+  #[test]
+  fn oklab_round_trips() {
+    for color in colors() {
+      let [l, a, b, alpha] = color.to_oklab();
+      assert_close(Color::oklab(l, a, b, alpha).to_srgb(), color.to_srgb());
+
+      let [l, c, h, alpha] = color.to_oklch();
+      assert_close(Color::oklch(l, c, h, alpha).to_srgb(), color.to_srgb());
+    }
+
+    // White is full lightness without any chroma
+    assert_close(
+      Color::new(1.0, 1.0, 1.0, 1.0).to_oklab(),
+      [1.0, 0.0, 0.0, 1.0],
+    );
+  }
+
+  #[test]
+  fn hsl_round_trips() {
+    for color in colors() {
+      let [h, s, l, alpha] = color.to_hsl();
+      assert_close(Color::hsl(h, s, l, alpha).to_srgb(), color.to_srgb());
+    }
+
+    assert_close(
+      Color::new(1.0, 0.0, 0.0, 1.0).to_hsl(),
+      [0.0, 1.0, 0.5, 1.0],
+    );
+    assert_close(
+      Color::new(0.0, 0.0, 1.0, 1.0).to_hsl(),
+      [240.0, 1.0, 0.5, 1.0],
+    );
+  }
+
+  #[test]
+  fn hues_take_the_shortest_path() {
+    assert!((interpolate_hue((350.0, 1.0), (10.0, 1.0), 0.5) - 0.0).abs() < 1e-9);
+    assert!((interpolate_hue((10.0, 1.0), (350.0, 1.0), 0.25) - 5.0).abs() < 1e-9);
+    assert!((interpolate_hue((90.0, 1.0), (180.0, 1.0), 0.5) - 135.0).abs() < 1e-9);
+
+    // Red to magenta goes backwards around the circle, not through green
+    let red = Color::new(1.0, 0.0, 0.0, 1.0);
+    let magenta = Color::new(1.0, 0.0, 1.0, 1.0);
+    let [h, ..] = ColorSpace::Hsl.interpolate(red, magenta, 0.5).to_hsl();
+    assert!((h - 330.0).abs() < 1e-6);
+  }
+
+  #[test]
+  fn greys_take_the_other_hue() {
+    assert_eq!(interpolate_hue((0.0, 0.0), (120.0, 1.0), 0.3), 120.0);
+    assert_eq!(interpolate_hue((200.0, 0.5), (0.0, 0.0), 0.7), 200.0);
+
+    let grey = Color::new(0.5, 0.5, 0.5, 1.0);
+    let blue = Color::new(0.0, 0.0, 1.0, 1.0);
+    let [h, ..] = ColorSpace::Hsl.interpolate(grey, blue, 0.5).to_hsl();
+    assert!((h - 240.0).abs() < 1e-6);
+    let [_, _, h, _] = ColorSpace::Oklch.interpolate(grey, blue, 0.5).to_oklch();
+    assert!((h - blue.to_oklch()[2]).abs() < 1e-6);
+  }
+
+  #[test]
+  fn endpoints_are_exact() {
+    let [a, b] = [colors()[2], colors()[3]];
+    for space in SPACES {
+      assert_eq!(space.interpolate(a, b, 0.0), a, "{space:?}");
+      assert_eq!(space.interpolate(a, b, 1.0), b, "{space:?}");
+      // Alpha is always linear
+      assert_eq!(space.interpolate(a, b, 0.5).a, 0.75, "{space:?}");
+    }
+  }
+}
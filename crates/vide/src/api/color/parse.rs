@@ -0,0 +1,499 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use super::Color;
+
+/// Error returned when a string can't be parsed as a [`Color`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseColorError {
+  /// A `#` color that doesn't have 3, 4, 6 or 8 hex digits
+  InvalidHex(String),
+  /// A function other than `rgb()`, `rgba()`, `hsl()`, `hsla()`, `oklab()` or
+  /// `oklch()`
+  UnknownFunction(String),
+  /// A color function with the wrong amount of components
+  ArgumentCount {
+    function: String,
+    expected: usize,
+    found: usize,
+  },
+  /// A component of a color function that isn't a valid number, percentage
+  /// or angle
+  InvalidComponent(String),
+  /// A name that isn't one of the CSS named colors
+  UnknownName(String),
+}
+
+impl fmt::Display for ParseColorError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::InvalidHex(hex) => write!(f, "invalid hex color `#{}`", hex),
+      Self::UnknownFunction(function) => write!(f, "unknown color function `{}()`", function),
+      Self::ArgumentCount {
+        function,
+        expected,
+        found,
+      } => write!(
+        f,
+        "`{}()` takes {} components, found {}",
+        function, expected, found
+      ),
+      Self::InvalidComponent(component) => write!(f, "invalid color component `{}`", component),
+      Self::UnknownName(name) => write!(f, "unknown color name `{}`", name),
+    }
+  }
+}
+
+impl Error for ParseColorError {}
+
+/// Parses colors using CSS syntax:
+///
+/// - Hex colors: `#rgb`, `#rgba`, `#rrggbb` and `#rrggbbaa`
+/// - `rgb()`/`rgba()` with components from `0` to `255` or percentages
+/// - `hsl()`/`hsla()` with the hue in degrees
+/// - `oklab()` and `oklch()`, with the lightness from `0.0` to `1.0` or as a
+///   percentage
+/// - CSS named colors, e.g. `rebeccapurple`, and `transparent`
+///
+/// Components can be separated by commas or spaces, alpha can be passed as
+/// a fourth component or after a `/`.
+///
+/// ```
+/// # use vide::api::color::Color;
+/// let brand: Color = "#da0037".parse().unwrap();
+/// let faded = Color::try_from("rgb(218 0 55 / 50%)").unwrap();
+/// let sky: Color = "oklch(70% 0.12 230)".parse().unwrap();
+/// ```
+impl FromStr for Color {
+  type Err = ParseColorError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let s = s.trim().to_ascii_lowercase();
+
+    if let Some(hex) = s.strip_prefix('#') {
+      parse_hex(hex)
+    } else if let Some((function, arguments)) = s.strip_suffix(')').and_then(|s| s.split_once('('))
+    {
+      parse_function(function.trim(), arguments)
+    } else {
+      named(&s).ok_or(ParseColorError::UnknownName(s))
+    }
+  }
+}
+
+impl TryFrom<&str> for Color {
+  type Error = ParseColorError;
+
+  fn try_from(value: &str) -> Result<Self, Self::Error> {
+    value.parse()
+  }
+}
+
+fn parse_hex(hex: &str) -> Result<Color, ParseColorError> {
+  let error = || ParseColorError::InvalidHex(hex.to_string());
+  if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+    return Err(error());
+  }
+
+  let digits = hex
+    .chars()
+    .map(|c| c.to_digit(16).unwrap() as u8)
+    .collect::<Vec<_>>();
+  let channels = match digits.len() {
+    // Short forms repeat every digit, `#f80` is `#ff8800`
+    3 | 4 => digits.iter().map(|d| d * 17).collect::<Vec<_>>(),
+    6 | 8 => digits.chunks(2).map(|d| d[0] * 16 + d[1]).collect(),
+    _ => return Err(error()),
+  };
+  let alpha = channels.get(3).copied().unwrap_or(255);
+
+  Ok(Color::new(
+    channels[0] as f64 / 255.0,
+    channels[1] as f64 / 255.0,
+    channels[2] as f64 / 255.0,
+    alpha as f64 / 255.0,
+  ))
+}
+
+fn parse_function(function: &str, arguments: &str) -> Result<Color, ParseColorError> {
+  let (channels, alpha) = match arguments.split_once('/') {
+    Some((channels, alpha)) => (channels, Some(alpha.trim())),
+    None => (arguments, None),
+  };
+  let mut components = channels
+    .split(|c: char| c == ',' || c.is_whitespace())
+    .filter(|component| !component.is_empty())
+    .collect::<Vec<_>>();
+
+  // Legacy syntax passes alpha as the fourth component
+  let alpha = match alpha {
+    Some(alpha) => Some(alpha),
+    None if components.len() == 4 => components.pop(),
+    None => None,
+  };
+  if components.len() != 3 {
+    return Err(ParseColorError::ArgumentCount {
+      function: function.to_string(),
+      expected: 3,
+      found: components.len(),
+    });
+  }
+
+  let alpha = alpha
+    .map(|alpha| number(alpha, 1.0))
+    .transpose()?
+    .unwrap_or(1.0);
+  let [a, b, c] = [components[0], components[1], components[2]];
+
+  match function {
+    "rgb" | "rgba" => Ok(Color::new(
+      number(a, 255.0)? / 255.0,
+      number(b, 255.0)? / 255.0,
+      number(c, 255.0)? / 255.0,
+      alpha,
+    )),
+    "hsl" | "hsla" => Ok(Color::hsl(
+      angle(a)?,
+      number(b, 100.0)? / 100.0,
+      number(c, 100.0)? / 100.0,
+      alpha,
+    )),
+    "oklab" => Ok(Color::oklab(
+      number(a, 1.0)?,
+      number(b, 0.4)?,
+      number(c, 0.4)?,
+      alpha,
+    )),
+    "oklch" => Ok(Color::oklch(
+      number(a, 1.0)?,
+      number(b, 0.4)?,
+      angle(c)?,
+      alpha,
+    )),
+    _ => Err(ParseColorError::UnknownFunction(function.to_string())),
+  }
+}
+
+/// Parses a number or a percentage of `full`
+fn number(component: &str, full: f64) -> Result<f64, ParseColorError> {
+  let error = || ParseColorError::InvalidComponent(component.to_string());
+  match component.strip_suffix('%') {
+    Some(percentage) => percentage
+      .parse::<f64>()
+      .map(|p| p / 100.0 * full)
+      .map_err(|_| error()),
+    None => component.parse().map_err(|_| error()),
+  }
+}
+
+/// Parses an angle in degrees, `deg` and `turn` units are optional
+fn angle(component: &str) -> Result<f64, ParseColorError> {
+  let error = || ParseColorError::InvalidComponent(component.to_string());
+  if let Some(turns) = component.strip_suffix("turn") {
+    turns.parse::<f64>().map(|t| t * 360.0).map_err(|_| error())
+  } else {
+    let degrees = component.strip_suffix("deg").unwrap_or(component);
+    degrees.parse().map_err(|_| error())
+  }
+}
+
+/// Looks up a CSS named color
+fn named(name: &str) -> Option<Color> {
+  if name == "transparent" {
+    return Some(Color::TRANSPARENT_BLACK);
+  }
+
+  let index = NAMED_COLORS
+    .binary_search_by_key(&name, |(name, _)| name)
+    .ok()?;
+  let rgb = NAMED_COLORS[index].1;
+
+  Some(Color::new(
+    (rgb >> 16 & 0xff) as f64 / 255.0,
+    (rgb >> 8 & 0xff) as f64 / 255.0,
+    (rgb & 0xff) as f64 / 255.0,
+    1.0,
+  ))
+}
+
+/// CSS named colors, sorted by name
+const NAMED_COLORS: [(&str, u32); 148] = [
+  ("aliceblue", 0xf0f8ff),
+  ("antiquewhite", 0xfaebd7),
+  ("aqua", 0x00ffff),
+  ("aquamarine", 0x7fffd4),
+  ("azure", 0xf0ffff),
+  ("beige", 0xf5f5dc),
+  ("bisque", 0xffe4c4),
+  ("black", 0x000000),
+  ("blanchedalmond", 0xffebcd),
+  ("blue", 0x0000ff),
+  ("blueviolet", 0x8a2be2),
+  ("brown", 0xa52a2a),
+  ("burlywood", 0xdeb887),
+  ("cadetblue", 0x5f9ea0),
+  ("chartreuse", 0x7fff00),
+  ("chocolate", 0xd2691e),
+  ("coral", 0xff7f50),
+  ("cornflowerblue", 0x6495ed),
+  ("cornsilk", 0xfff8dc),
+  ("crimson", 0xdc143c),
+  ("cyan", 0x00ffff),
+  ("darkblue", 0x00008b),
+  ("darkcyan", 0x008b8b),
+  ("darkgoldenrod", 0xb8860b),
+  ("darkgray", 0xa9a9a9),
+  ("darkgreen", 0x006400),
+  ("darkgrey", 0xa9a9a9),
+  ("darkkhaki", 0xbdb76b),
+  ("darkmagenta", 0x8b008b),
+  ("darkolivegreen", 0x556b2f),
+  ("darkorange", 0xff8c00),
+  ("darkorchid", 0x9932cc),
+  ("darkred", 0x8b0000),
+  ("darksalmon", 0xe9967a),
+  ("darkseagreen", 0x8fbc8f),
+  ("darkslateblue", 0x483d8b),
+  ("darkslategray", 0x2f4f4f),
+  ("darkslategrey", 0x2f4f4f),
+  ("darkturquoise", 0x00ced1),
+  ("darkviolet", 0x9400d3),
+  ("deeppink", 0xff1493),
+  ("deepskyblue", 0x00bfff),
+  ("dimgray", 0x696969),
+  ("dimgrey", 0x696969),
+  ("dodgerblue", 0x1e90ff),
+  ("firebrick", 0xb22222),
+  ("floralwhite", 0xfffaf0),
+  ("forestgreen", 0x228b22),
+  ("fuchsia", 0xff00ff),
+  ("gainsboro", 0xdcdcdc),
+  ("ghostwhite", 0xf8f8ff),
+  ("gold", 0xffd700),
+  ("goldenrod", 0xdaa520),
+  ("gray", 0x808080),
+  ("green", 0x008000),
+  ("greenyellow", 0xadff2f),
+  ("grey", 0x808080),
+  ("honeydew", 0xf0fff0),
+  ("hotpink", 0xff69b4),
+  ("indianred", 0xcd5c5c),
+  ("indigo", 0x4b0082),
+  ("ivory", 0xfffff0),
+  ("khaki", 0xf0e68c),
+  ("lavender", 0xe6e6fa),
+  ("lavenderblush", 0xfff0f5),
+  ("lawngreen", 0x7cfc00),
+  ("lemonchiffon", 0xfffacd),
+  ("lightblue", 0xadd8e6),
+  ("lightcoral", 0xf08080),
+  ("lightcyan", 0xe0ffff),
+  ("lightgoldenrodyellow", 0xfafad2),
+  ("lightgray", 0xd3d3d3),
+  ("lightgreen", 0x90ee90),
+  ("lightgrey", 0xd3d3d3),
+  ("lightpink", 0xffb6c1),
+  ("lightsalmon", 0xffa07a),
+  ("lightseagreen", 0x20b2aa),
+  ("lightskyblue", 0x87cefa),
+  ("lightslategray", 0x778899),
+  ("lightslategrey", 0x778899),
+  ("lightsteelblue", 0xb0c4de),
+  ("lightyellow", 0xffffe0),
+  ("lime", 0x00ff00),
+  ("limegreen", 0x32cd32),
+  ("linen", 0xfaf0e6),
+  ("magenta", 0xff00ff),
+  ("maroon", 0x800000),
+  ("mediumaquamarine", 0x66cdaa),
+  ("mediumblue", 0x0000cd),
+  ("mediumorchid", 0xba55d3),
+  ("mediumpurple", 0x9370db),
+  ("mediumseagreen", 0x3cb371),
+  ("mediumslateblue", 0x7b68ee),
+  ("mediumspringgreen", 0x00fa9a),
+  ("mediumturquoise", 0x48d1cc),
+  ("mediumvioletred", 0xc71585),
+  ("midnightblue", 0x191970),
+  ("mintcream", 0xf5fffa),
+  ("mistyrose", 0xffe4e1),
+  ("moccasin", 0xffe4b5),
+  ("navajowhite", 0xffdead),
+  ("navy", 0x000080),
+  ("oldlace", 0xfdf5e6),
+  ("olive", 0x808000),
+  ("olivedrab", 0x6b8e23),
+  ("orange", 0xffa500),
+  ("orangered", 0xff4500),
+  ("orchid", 0xda70d6),
+  ("palegoldenrod", 0xeee8aa),
+  ("palegreen", 0x98fb98),
+  ("paleturquoise", 0xafeeee),
+  ("palevioletred", 0xdb7093),
+  ("papayawhip", 0xffefd5),
+  ("peachpuff", 0xffdab9),
+  ("peru", 0xcd853f),
+  ("pink", 0xffc0cb),
+  ("plum", 0xdda0dd),
+  ("powderblue", 0xb0e0e6),
+  ("purple", 0x800080),
+  ("rebeccapurple", 0x663399),
+  ("red", 0xff0000),
+  ("rosybrown", 0xbc8f8f),
+  ("royalblue", 0x4169e1),
+  ("saddlebrown", 0x8b4513),
+  ("salmon", 0xfa8072),
+  ("sandybrown", 0xf4a460),
+  ("seagreen", 0x2e8b57),
+  ("seashell", 0xfff5ee),
+  ("sienna", 0xa0522d),
+  ("silver", 0xc0c0c0),
+  ("skyblue", 0x87ceeb),
+  ("slateblue", 0x6a5acd),
+  ("slategray", 0x708090),
+  ("slategrey", 0x708090),
+  ("snow", 0xfffafa),
+  ("springgreen", 0x00ff7f),
+  ("steelblue", 0x4682b4),
+  ("tan", 0xd2b48c),
+  ("teal", 0x008080),
+  ("thistle", 0xd8bfd8),
+  ("tomato", 0xff6347),
+  ("turquoise", 0x40e0d0),
+  ("violet", 0xee82ee),
+  ("wheat", 0xf5deb3),
+  ("white", 0xffffff),
+  ("whitesmoke", 0xf5f5f5),
+  ("yellow", 0xffff00),
+  ("yellowgreen", 0x9acd32),
+];
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse(s: &str) -> Color {
+    s.parse()
+      .unwrap_or_else(|error| panic!("failed to parse {s:?}: {error}"))
+  }
+
+  fn assert_color(actual: Color, expected: Color) {
+    let (a, e): ([f64; 4], [f64; 4]) = (actual.into(), expected.into());
+    assert!(
+      a.iter().zip(e).all(|(a, e)| (a - e).abs() < 1e-6),
+      "expected {expected:?}, got {actual:?}"
+    );
+  }
+
+  #[test]
+  fn parses_hex_colors() {
+    let brand = crate::rgb8!(0xda, 0x00, 0x37);
+    assert_color(parse("#da0037"), brand);
+    assert_color(parse("  #DA0037 "), brand);
+    assert_color(parse("#da0037ff"), brand);
+    assert_color(parse("#f80"), crate::rgb8!(0xff, 0x88, 0x00));
+    assert_color(parse("#f808"), crate::rgba8!(0xff, 0x88, 0x00, 0x88));
+  }
+
+  #[test]
+  fn parses_color_functions() {
+    let faded = crate::rgba8!(0xda, 0x00, 0x37, 0x80);
+    assert_color(parse("rgb(218 0 55 / 50%)"), Color { a: 0.5, ..faded });
+    assert_color(parse("rgba(218, 0, 55, 0.5)"), Color { a: 0.5, ..faded });
+    assert_color(parse("rgb(100% 0% 0%)"), Color::RED);
+    assert_color(parse("hsl(120deg 100% 50%)"), Color::GREEN);
+    assert_color(parse("hsla(0.5turn, 0%, 100%, 1)"), Color::WHITE);
+    assert_color(parse("oklab(0% 0 0)"), Color::BLACK);
+    assert_color(parse("oklch(100% 0 0)"), Color::WHITE);
+  }
+
+  #[test]
+  fn looks_up_named_colors() {
+    assert_color(parse("rebeccapurple"), crate::rgb8!(0x66, 0x33, 0x99));
+    assert_color(parse("AliceBlue"), crate::rgb8!(0xf0, 0xf8, 0xff));
+    assert_color(parse("yellowgreen"), crate::rgb8!(0x9a, 0xcd, 0x32));
+    assert_color(parse("white"), Color::WHITE);
+  }
+
+  #[test]
+  fn named_colors_are_sorted() {
+    // Looking up names relies on a binary search
+    assert!(NAMED_COLORS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+  }
+
+  #[test]
+  fn transparent_is_transparent_black() {
+    assert_eq!(parse("transparent"), Color::TRANSPARENT_BLACK);
+    assert_eq!(
+      Color::TRANSPARENT_BLACK,
+      Color::from_raw(0.0, 0.0, 0.0, 0.0)
+    );
+  }
+
+  #[test]
+  fn rejects_hex_colors_with_the_wrong_length() {
+    for hex in ["", "f", "ff", "fffff", "fffffff", "fffffffff"] {
+      assert_eq!(
+        format!("#{hex}").parse::<Color>(),
+        Err(ParseColorError::InvalidHex(hex.to_string()))
+      );
+    }
+  }
+
+  #[test]
+  fn rejects_invalid_hex_digits() {
+    assert_eq!(
+      "#ff00gg".parse::<Color>(),
+      Err(ParseColorError::InvalidHex("ff00gg".to_string()))
+    );
+  }
+
+  #[test]
+  fn rejects_unknown_names() {
+    assert_eq!(
+      "blurple".parse::<Color>(),
+      Err(ParseColorError::UnknownName("blurple".to_string()))
+    );
+  }
+
+  #[test]
+  fn rejects_unknown_functions() {
+    assert_eq!(
+      "cmyk(0 0 0)".parse::<Color>(),
+      Err(ParseColorError::UnknownFunction("cmyk".to_string()))
+    );
+  }
+
+  #[test]
+  fn rejects_the_wrong_amount_of_components() {
+    for (color, found) in [("rgb(1 2)", 2), ("rgb(1, 2, 3, 4, 5)", 5), ("rgb()", 0)] {
+      assert_eq!(
+        color.parse::<Color>(),
+        Err(ParseColorError::ArgumentCount {
+          function: "rgb".to_string(),
+          expected: 3,
+          found,
+        })
+      );
+    }
+  }
+
+  #[test]
+  fn rejects_invalid_components() {
+    for (color, component) in [
+      ("rgb(1 two 3)", "two"),
+      ("hsl(12rad 50% 50%)", "12rad"),
+      ("rgb(1 2 3 / half)", "half"),
+    ] {
+      assert_eq!(
+        color.parse::<Color>(),
+        Err(ParseColorError::InvalidComponent(component.to_string()))
+      );
+    }
+  }
+
+  #[test]
+  fn errors_describe_the_problem() {
+    let error = "rgb(1 2)".parse::<Color>().unwrap_err();
+    assert_eq!(error.to_string(), "`rgb()` takes 3 components, found 2");
+  }
+}
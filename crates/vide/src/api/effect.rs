@@ -0,0 +1,207 @@
+pub mod bloom;
+pub mod blur;
+pub mod chromatic_aberration;
+pub mod color_adjust;
+pub mod vignette;
+
+use std::sync::{Mutex, MutexGuard};
+
+use super::fullscreen::FullscreenPass;
+use crate::{
+  clip::Clip,
+  render::{RenderTarget, Renderer, Time},
+};
+
+/// A full-screen render pass, applied to the whole video (see
+/// [`Video::push_effect`](super::video::Video::push_effect)) or to a single
+/// [`Layer`]
+pub trait Effect {
+  /// Reads the image in `input` and writes the processed image to `output`.
+  /// Both targets contain premultiplied colors. `time` is the timing of
+  /// whatever the effect is applied to and should be used to evaluate animated
+  /// parameters
+  fn apply(
+    &mut self,
+    renderer: &mut Renderer,
+    input: &RenderTarget,
+    output: &RenderTarget,
+    time: Time,
+  );
+}
+
+struct EffectStackResources {
+  targets: [RenderTarget; 2],
+  composite: FullscreenPass,
+}
+
+/// A chain of [`Effect`]s, each effect receives the output of the previous one
+pub struct EffectStack {
+  effects: Vec<Box<dyn Effect>>,
+  blend: Option<wgpu::BlendState>,
+  resources: Option<EffectStackResources>,
+}
+
+impl Default for EffectStack {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl EffectStack {
+  /// Creates an empty stack whose result is blended over whatever is already
+  /// in the render pass
+  pub fn new() -> Self {
+    Self {
+      effects: vec![],
+      blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+      resources: None,
+    }
+  }
+
+  /// Creates an empty stack whose result replaces whatever is already in the
+  /// render pass, used for effects on the whole video
+  pub(crate) fn opaque() -> Self {
+    Self {
+      blend: None,
+      ..Self::new()
+    }
+  }
+
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.effects.is_empty()
+  }
+
+  #[inline]
+  pub fn push(&mut self, effect: impl Effect + 'static) {
+    self.effects.push(Box::new(effect));
+  }
+
+  /// Renders `content` into an offscreen target cleared to `clear`, runs it
+  /// through every effect and draws the result into `pass`
+  pub fn render<F>(
+    &mut self,
+    renderer: &mut Renderer,
+    pass: MutexGuard<wgpu::RenderPass<'_>>,
+    time: Time,
+    clear: wgpu::Color,
+    content: F,
+  ) where
+    F: FnOnce(&mut Renderer, &Mutex<wgpu::RenderPass<'_>>),
+  {
+    let blend = self.blend;
+    let resources = self.resources.get_or_insert_with(|| EffectStackResources {
+      targets: [
+        renderer.create_render_target(),
+        renderer.create_render_target(),
+      ],
+      composite: FullscreenPass::new(renderer, include_str!("effect/composite.wgsl"), 1, 0, blend),
+    });
+
+    let [front, back] = &resources.targets;
+    let (mut input, mut output) = (front, back);
+
+    renderer.render_to_target(input, clear, content);
+    for effect in self.effects.iter_mut() {
+      effect.apply(renderer, input, output, time);
+      std::mem::swap(&mut input, &mut output);
+    }
+
+    resources.composite.render(renderer, pass, &[], &[input]);
+  }
+}
+
+/// Wraps a clip to apply [`Effect`]s to it, without affecting the rest of the
+/// video. Timing follows the wrapped clip
+pub struct Layer {
+  pub clip: Box<dyn Clip>,
+  pub effects: EffectStack,
+}
+
+impl Layer {
+  pub fn builder() -> LayerBuilder {
+    LayerBuilder::default()
+  }
+}
+
+impl Clip for Layer {
+  fn start(&self, fps: f64) -> u64 {
+    self.clip.start(fps)
+  }
+
+  fn end(&self, video_end: u64, fps: f64) -> u64 {
+    self.clip.end(video_end, fps)
+  }
+
+  fn in_time_frame(&self, frame: u64, fps: f64) -> bool {
+    self.clip.in_time_frame(frame, fps)
+  }
+
+  fn render(&mut self, renderer: &mut Renderer, pass: &Mutex<wgpu::RenderPass<'_>>, time: Time) {
+    let clip = &mut self.clip;
+    self.effects.render(
+      renderer,
+      pass.lock().unwrap(),
+      time,
+      wgpu::Color::TRANSPARENT,
+      |renderer, pass| clip.render(renderer, pass, time),
+    );
+  }
+}
+
+#[derive(Default)]
+pub struct LayerBuilder {
+  clip: Option<Box<dyn Clip>>,
+  effects: EffectStack,
+}
+
+impl LayerBuilder {
+  pub fn clip(mut self, clip: impl Clip + 'static) -> Self {
+    self.clip = Some(Box::new(clip));
+    self
+  }
+
+  /// Effects are applied in the order they are added
+  pub fn effect(mut self, effect: impl Effect + 'static) -> Self {
+    self.effects.push(effect);
+    self
+  }
+
+  pub fn build(self) -> Layer {
+    Layer {
+      clip: self.clip.expect("layer is missing its clip"),
+      effects: self.effects,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{color_adjust::ColorAdjust, *};
+  use crate::api::rect::Rect;
+
+  #[test]
+  fn layers_follow_their_clip() {
+    let layer = Layer::builder()
+      .clip(Rect::builder().timing(1.0..f64::INFINITY).build())
+      .effect(ColorAdjust::new(0.0, 1.0, 0.0))
+      .build();
+
+    assert_eq!(layer.start(10.0), 10);
+    assert_eq!(layer.end(50, 10.0), 50);
+    assert!(!layer.in_time_frame(9, 10.0));
+    assert!(layer.in_time_frame(10, 10.0));
+    assert!(!layer.effects.is_empty());
+  }
+
+  #[test]
+  fn stacks_blend_unless_opaque() {
+    let stack = EffectStack::new();
+    assert!(stack.is_empty());
+    assert_eq!(
+      stack.blend,
+      Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING)
+    );
+    assert_eq!(EffectStack::opaque().blend, None);
+  }
+}
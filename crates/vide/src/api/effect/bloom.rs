@@ -0,0 +1,114 @@
+use bytemuck::{Pod, Zeroable};
+
+use super::{blur::GaussianBlur, Effect};
+use crate::{
+  api::{animation::Animated, fullscreen::FullscreenPass},
+  render::{RenderTarget, Renderer, Time},
+};
+
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+struct BloomUniform {
+  threshold: f32,
+  intensity: f32,
+  _padding: [f32; 2],
+}
+
+struct BloomResources {
+  bright: RenderTarget,
+  glow: RenderTarget,
+  threshold: FullscreenPass,
+  combine: FullscreenPass,
+}
+
+/// Makes bright parts of the image glow
+pub struct Bloom {
+  /// Luminance (`0.0..=1.0`) above which pixels start to glow
+  pub threshold: Animated<f32>,
+  /// How strong the glow is added on top of the image, `0.0` disables it
+  pub intensity: Animated<f32>,
+  blur: GaussianBlur,
+  resources: Option<BloomResources>,
+}
+
+impl Bloom {
+  /// `radius` is the blur radius of the glow in pixels
+  pub fn new(
+    threshold: impl Into<Animated<f32>>,
+    intensity: impl Into<Animated<f32>>,
+    radius: impl Into<Animated<f32>>,
+  ) -> Self {
+    Self {
+      threshold: threshold.into(),
+      intensity: intensity.into(),
+      blur: GaussianBlur::new(radius),
+      resources: None,
+    }
+  }
+
+  fn uniform(&self, time: Time) -> BloomUniform {
+    BloomUniform {
+      threshold: self.threshold.evaluate(time).max(0.0),
+      intensity: self.intensity.evaluate(time).max(0.0),
+      _padding: [0.0; 2],
+    }
+  }
+}
+
+impl Effect for Bloom {
+  fn apply(
+    &mut self,
+    renderer: &mut Renderer,
+    input: &RenderTarget,
+    output: &RenderTarget,
+    time: Time,
+  ) {
+    let uniform = self.uniform(time);
+    let uniform_size = std::mem::size_of::<BloomUniform>() as u64;
+    let resources = self.resources.get_or_insert_with(|| BloomResources {
+      bright: renderer.create_render_target(),
+      glow: renderer.create_render_target(),
+      threshold: FullscreenPass::new(
+        renderer,
+        include_str!("bloom_threshold.wgsl"),
+        1,
+        uniform_size,
+        None,
+      ),
+      combine: FullscreenPass::new(
+        renderer,
+        include_str!("bloom_combine.wgsl"),
+        2,
+        uniform_size,
+        None,
+      ),
+    });
+
+    let uniform = bytemuck::bytes_of(&uniform);
+
+    resources
+      .threshold
+      .render_to(renderer, &resources.bright, uniform, &[input]);
+    let radius = self.blur.radius.evaluate(time);
+    self
+      .blur
+      .blur(renderer, &resources.bright, &resources.glow, radius);
+    resources
+      .combine
+      .render_to(renderer, output, uniform, &[input, &resources.glow]);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parameters_are_clamped() {
+    let uniform = Bloom::new(0.8, 1.5, 10.0).uniform(Time::default());
+    assert_eq!((uniform.threshold, uniform.intensity), (0.8, 1.5));
+
+    let uniform = Bloom::new(-0.2, -1.0, 10.0).uniform(Time::default());
+    assert_eq!((uniform.threshold, uniform.intensity), (0.0, 0.0));
+  }
+}
@@ -0,0 +1,28 @@
+struct BloomUniform {
+  threshold: f32,
+  intensity: f32,
+};
+
+@group(1)
+@binding(0)
+var<uniform> bloom_uniform: BloomUniform;
+
+@group(1)
+@binding(1)
+var input_sampler: sampler;
+
+@group(1)
+@binding(2)
+var input_texture: texture_2d<f32>;
+
+@group(1)
+@binding(3)
+var glow_texture: texture_2d<f32>;
+
+// Adds the blurred bright parts on top of the original image
+@fragment
+fn fs_main(in: FullscreenOutput) -> @location(0) vec4<f32> {
+  let color = textureSample(input_texture, input_sampler, in.uv);
+  let glow = textureSample(glow_texture, input_sampler, in.uv) * bloom_uniform.intensity;
+  return vec4(color.rgb + glow.rgb, clamp(color.a + glow.a, 0.0, 1.0));
+}
@@ -0,0 +1,26 @@
+struct BloomUniform {
+  threshold: f32,
+  intensity: f32,
+};
+
+@group(1)
+@binding(0)
+var<uniform> bloom_uniform: BloomUniform;
+
+@group(1)
+@binding(1)
+var input_sampler: sampler;
+
+@group(1)
+@binding(2)
+var input_texture: texture_2d<f32>;
+
+// Keeps the parts of the image brighter than the threshold, with a soft knee
+@fragment
+fn fs_main(in: FullscreenOutput) -> @location(0) vec4<f32> {
+  let color = textureSample(input_texture, input_sampler, in.uv);
+  let luminance = dot(color.rgb, vec3(0.2126, 0.7152, 0.0722));
+  let knee = max(bloom_uniform.threshold * 0.5, 0.0001);
+  let amount = smoothstep(bloom_uniform.threshold - knee, bloom_uniform.threshold + knee, luminance);
+  return color * amount;
+}
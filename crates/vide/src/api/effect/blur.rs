@@ -0,0 +1,166 @@
+use bytemuck::{Pod, Zeroable};
+
+use super::Effect;
+use crate::{
+  api::{animation::Animated, fullscreen::FullscreenPass},
+  render::{RenderTarget, Renderer, Time},
+};
+
+/// Radius (in pixels) above which the blur gets clamped, larger radii get
+/// expensive quickly
+pub const MAX_BLUR_RADIUS: f32 = 64.0;
+
+/// Kernel weights from the center outwards, packed into vectors for the
+/// uniform's array stride
+const KERNEL_VECTORS: usize = (MAX_BLUR_RADIUS as usize + 1).div_ceil(4);
+
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+struct BlurUniform {
+  direction: [f32; 2],
+  /// Samples on each side of the center
+  samples: u32,
+  _padding: f32,
+  weights: [[f32; 4]; KERNEL_VECTORS],
+}
+
+impl BlurUniform {
+  /// Blurs by `radius` pixels along `direction`, one texel long
+  fn new(direction: [f32; 2], radius: f32) -> Self {
+    let radius = radius.clamp(0.0, MAX_BLUR_RADIUS);
+    let samples = radius.ceil() as usize;
+    // The kernel covers 3 standard deviations
+    let sigma = (radius / 3.0).max(0.0001);
+
+    let mut weights = [[0.0; 4]; KERNEL_VECTORS];
+    let mut total = 0.0;
+    for i in 0..=samples {
+      let weight = (-((i * i) as f32) / (2.0 * sigma * sigma)).exp();
+      weights[i / 4][i % 4] = weight;
+      total += if i == 0 { weight } else { 2.0 * weight };
+    }
+    for weight in weights.iter_mut().flatten() {
+      *weight /= total;
+    }
+
+    Self {
+      direction,
+      samples: samples as u32,
+      _padding: 0.0,
+      weights,
+    }
+  }
+}
+
+/// Separable gaussian blur, `radius` is in pixels of the output resolution
+pub struct GaussianBlur {
+  pub radius: Animated<f32>,
+  resources: Option<(RenderTarget, FullscreenPass)>,
+}
+
+impl GaussianBlur {
+  pub fn new(radius: impl Into<Animated<f32>>) -> Self {
+    Self {
+      radius: radius.into(),
+      resources: None,
+    }
+  }
+
+  pub(super) fn blur(
+    &mut self,
+    renderer: &mut Renderer,
+    input: &RenderTarget,
+    output: &RenderTarget,
+    radius: f32,
+  ) {
+    let (scratch, shader) = self.resources.get_or_insert_with(|| {
+      (
+        renderer.create_render_target(),
+        FullscreenPass::new(
+          renderer,
+          include_str!("blur.wgsl"),
+          1,
+          std::mem::size_of::<BlurUniform>() as u64,
+          None,
+        ),
+      )
+    });
+
+    let (width, height) = renderer.settings.resolution;
+
+    let horizontal = BlurUniform::new([1.0 / width as f32, 0.0], radius);
+    shader.render_to(renderer, scratch, bytemuck::bytes_of(&horizontal), &[input]);
+
+    let vertical = BlurUniform::new([0.0, 1.0 / height as f32], radius);
+    shader.render_to(renderer, output, bytemuck::bytes_of(&vertical), &[scratch]);
+  }
+}
+
+impl Effect for GaussianBlur {
+  fn apply(
+    &mut self,
+    renderer: &mut Renderer,
+    input: &RenderTarget,
+    output: &RenderTarget,
+    time: Time,
+  ) {
+    let radius = self.radius.evaluate(time);
+    self.blur(renderer, input, output, radius);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn kernel(radius: f32) -> Vec<f32> {
+    let uniform = BlurUniform::new([1.0, 0.0], radius);
+    let weights = uniform.weights.as_flattened();
+    (-(uniform.samples as i32)..=uniform.samples as i32)
+      .map(|i| weights[i.unsigned_abs() as usize])
+      .collect()
+  }
+
+  #[test]
+  fn weights_sum_to_one() {
+    for radius in [0.5, 1.0, 2.5, 10.0, MAX_BLUR_RADIUS] {
+      let total: f32 = kernel(radius).iter().sum();
+      assert!((total - 1.0).abs() < 1e-5, "{radius}: {total}");
+    }
+  }
+
+  #[test]
+  fn weights_fall_off_from_the_center() {
+    let uniform = BlurUniform::new([1.0, 0.0], 6.0);
+    assert_eq!(uniform.samples, 6);
+
+    let weights = &uniform.weights.as_flattened()[..=6];
+    assert!(weights.windows(2).all(|pair| pair[0] > pair[1]));
+    // 3 standard deviations out
+    assert!((weights[6] / weights[0] - (-4.5f32).exp()).abs() < 1e-6);
+    assert!(uniform.weights.as_flattened()[7..]
+      .iter()
+      .all(|w| *w == 0.0));
+  }
+
+  #[test]
+  fn zero_radius_is_the_identity() {
+    assert_eq!(kernel(0.0), [1.0]);
+    assert_eq!(kernel(-5.0), [1.0]);
+  }
+
+  #[test]
+  fn radius_is_clamped() {
+    let uniform = BlurUniform::new([0.0, 0.5], 1000.0);
+    assert_eq!(uniform.samples, MAX_BLUR_RADIUS as u32);
+    assert_eq!(uniform.direction, [0.0, 0.5]);
+  }
+
+  #[test]
+  fn uniform_matches_the_shader_layout() {
+    // `weights` is an array of `vec4<f32>`, starting after 16 bytes
+    assert_eq!(std::mem::offset_of!(BlurUniform, weights), 16);
+    assert_eq!(std::mem::size_of::<BlurUniform>(), 16 + 16 * KERNEL_VECTORS);
+    assert!(include_str!("blur.wgsl").contains(&format!("array<vec4<f32>, {KERNEL_VECTORS}>")));
+  }
+}
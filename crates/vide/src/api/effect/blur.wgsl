@@ -0,0 +1,35 @@
+struct BlurUniform {
+  // One texel along the blur direction
+  direction: vec2<f32>,
+  // Samples on each side of the center
+  samples: u32,
+  // Normalized kernel weights from the center outwards, 4 per vector
+  weights: array<vec4<f32>, 17>,
+};
+
+@group(1)
+@binding(0)
+var<uniform> blur_uniform: BlurUniform;
+
+@group(1)
+@binding(1)
+var input_sampler: sampler;
+
+@group(1)
+@binding(2)
+var input_texture: texture_2d<f32>;
+
+@fragment
+fn fs_main(in: FullscreenOutput) -> @location(0) vec4<f32> {
+  let samples = i32(blur_uniform.samples);
+
+  var color = vec4(0.0);
+  for (var i = -samples; i <= samples; i++) {
+    let distance = u32(abs(i));
+    let weight = blur_uniform.weights[distance / 4u][distance % 4u];
+    let uv = in.uv + blur_uniform.direction * f32(i);
+    color += textureSampleLevel(input_texture, input_sampler, uv, 0.0) * weight;
+  }
+
+  return color;
+}
@@ -0,0 +1,75 @@
+use bytemuck::{Pod, Zeroable};
+
+use super::Effect;
+use crate::{
+  api::{animation::Animated, fullscreen::FullscreenPass},
+  render::{RenderTarget, Renderer, Time},
+};
+
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+struct ChromaticAberrationUniform {
+  offset: [f32; 2],
+  _padding: [f32; 2],
+}
+
+/// Splits the red and blue channels apart towards the edges of the image, like
+/// a cheap lens would
+pub struct ChromaticAberration {
+  /// How far (in pixels) the channels are displaced in the corners
+  pub offset: Animated<f32>,
+  shader: Option<FullscreenPass>,
+}
+
+impl ChromaticAberration {
+  pub fn new(offset: impl Into<Animated<f32>>) -> Self {
+    Self {
+      offset: offset.into(),
+      shader: None,
+    }
+  }
+
+  /// Converts the offset to uv space of a frame in `resolution`
+  fn uniform(&self, time: Time, (width, height): (u32, u32)) -> ChromaticAberrationUniform {
+    let offset = self.offset.evaluate(time);
+    ChromaticAberrationUniform {
+      offset: [offset / width as f32, offset / height as f32],
+      _padding: [0.0; 2],
+    }
+  }
+}
+
+impl Effect for ChromaticAberration {
+  fn apply(
+    &mut self,
+    renderer: &mut Renderer,
+    input: &RenderTarget,
+    output: &RenderTarget,
+    time: Time,
+  ) {
+    let uniform = self.uniform(time, renderer.settings.resolution);
+    let shader = self.shader.get_or_insert_with(|| {
+      FullscreenPass::new(
+        renderer,
+        include_str!("chromatic_aberration.wgsl"),
+        1,
+        std::mem::size_of::<ChromaticAberrationUniform>() as u64,
+        None,
+      )
+    });
+
+    shader.render_to(renderer, output, bytemuck::bytes_of(&uniform), &[input]);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn offsets_are_in_pixels() {
+    let uniform = ChromaticAberration::new(8.0).uniform(Time::default(), (1600, 800));
+    assert_eq!(uniform.offset, [0.005, 0.01]);
+    assert_eq!(std::mem::size_of::<ChromaticAberrationUniform>(), 16);
+  }
+}
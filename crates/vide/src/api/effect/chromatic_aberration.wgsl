@@ -0,0 +1,28 @@
+struct ChromaticAberrationUniform {
+  // Offset of the red and blue channels at the corners, in uv units
+  offset: vec2<f32>,
+};
+
+@group(1)
+@binding(0)
+var<uniform> aberration_uniform: ChromaticAberrationUniform;
+
+@group(1)
+@binding(1)
+var input_sampler: sampler;
+
+@group(1)
+@binding(2)
+var input_texture: texture_2d<f32>;
+
+@fragment
+fn fs_main(in: FullscreenOutput) -> @location(0) vec4<f32> {
+  // Channels drift apart towards the edges of the image
+  let direction = (in.uv - vec2(0.5)) * 2.0 * aberration_uniform.offset;
+
+  let red = textureSample(input_texture, input_sampler, in.uv + direction);
+  let green = textureSample(input_texture, input_sampler, in.uv);
+  let blue = textureSample(input_texture, input_sampler, in.uv - direction);
+
+  return vec4(red.r, green.g, blue.b, max(max(red.a, green.a), blue.a));
+}
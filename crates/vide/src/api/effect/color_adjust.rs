@@ -0,0 +1,96 @@
+use bytemuck::{Pod, Zeroable};
+
+use super::Effect;
+use crate::{
+  api::{animation::Animated, fullscreen::FullscreenPass},
+  render::{RenderTarget, Renderer, Time},
+};
+
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+struct ColorAdjustUniform {
+  brightness: f32,
+  contrast: f32,
+  saturation: f32,
+  _padding: f32,
+}
+
+/// Basic color grading
+pub struct ColorAdjust {
+  /// Added to every channel, `0.0` leaves the image unchanged
+  pub brightness: Animated<f32>,
+  /// Scales the distance of every channel from `0.5`, `1.0` leaves the image
+  /// unchanged. Negative values are treated as `0.0`
+  pub contrast: Animated<f32>,
+  /// `0.0` is greyscale, `1.0` leaves the image unchanged, values above `1.0`
+  /// oversaturate. Negative values are treated as `0.0`
+  pub saturation: Animated<f32>,
+  shader: Option<FullscreenPass>,
+}
+
+impl ColorAdjust {
+  pub fn new(
+    brightness: impl Into<Animated<f32>>,
+    contrast: impl Into<Animated<f32>>,
+    saturation: impl Into<Animated<f32>>,
+  ) -> Self {
+    Self {
+      brightness: brightness.into(),
+      contrast: contrast.into(),
+      saturation: saturation.into(),
+      shader: None,
+    }
+  }
+
+  fn uniform(&self, time: Time) -> ColorAdjustUniform {
+    ColorAdjustUniform {
+      brightness: self.brightness.evaluate(time),
+      contrast: self.contrast.evaluate(time).max(0.0),
+      saturation: self.saturation.evaluate(time).max(0.0),
+      _padding: 0.0,
+    }
+  }
+}
+
+impl Effect for ColorAdjust {
+  fn apply(
+    &mut self,
+    renderer: &mut Renderer,
+    input: &RenderTarget,
+    output: &RenderTarget,
+    time: Time,
+  ) {
+    let uniform = self.uniform(time);
+    let shader = self.shader.get_or_insert_with(|| {
+      FullscreenPass::new(
+        renderer,
+        include_str!("color_adjust.wgsl"),
+        1,
+        std::mem::size_of::<ColorAdjustUniform>() as u64,
+        None,
+      )
+    });
+
+    shader.render_to(renderer, output, bytemuck::bytes_of(&uniform), &[input]);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parameters_are_packed_in_order() {
+    let uniform = ColorAdjust::new(0.1, 1.2, 0.8).uniform(Time::default());
+    assert_eq!(bytemuck::cast::<_, [f32; 4]>(uniform), [0.1, 1.2, 0.8, 0.0]);
+  }
+
+  #[test]
+  fn contrast_and_saturation_are_clamped() {
+    let uniform = ColorAdjust::new(-0.5, -1.0, -2.0).uniform(Time::default());
+    assert_eq!(
+      (uniform.brightness, uniform.contrast, uniform.saturation),
+      (-0.5, 0.0, 0.0)
+    );
+  }
+}
@@ -0,0 +1,34 @@
+struct ColorAdjustUniform {
+  brightness: f32,
+  contrast: f32,
+  saturation: f32,
+};
+
+@group(1)
+@binding(0)
+var<uniform> adjust_uniform: ColorAdjustUniform;
+
+@group(1)
+@binding(1)
+var input_sampler: sampler;
+
+@group(1)
+@binding(2)
+var input_texture: texture_2d<f32>;
+
+@fragment
+fn fs_main(in: FullscreenOutput) -> @location(0) vec4<f32> {
+  let color = textureSample(input_texture, input_sampler, in.uv);
+  if (color.a <= 0.0) {
+    return color;
+  }
+
+  // Adjustments are done on straight (not premultiplied) colors
+  var rgb = color.rgb / color.a;
+  rgb = rgb + vec3(adjust_uniform.brightness);
+  rgb = (rgb - vec3(0.5)) * adjust_uniform.contrast + vec3(0.5);
+  let luminance = dot(rgb, vec3(0.2126, 0.7152, 0.0722));
+  rgb = mix(vec3(luminance), rgb, adjust_uniform.saturation);
+
+  return vec4(clamp(rgb, vec3(0.0), vec3(1.0)) * color.a, color.a);
+}
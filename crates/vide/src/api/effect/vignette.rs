@@ -0,0 +1,106 @@
+use bytemuck::{Pod, Zeroable};
+
+use super::Effect;
+use crate::{
+  api::{animation::Animated, fullscreen::FullscreenPass},
+  render::{RenderTarget, Renderer, Time},
+};
+
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+struct VignetteUniform {
+  intensity: f32,
+  radius: f32,
+  softness: f32,
+  _padding: f32,
+}
+
+/// Darkens the edges of the image
+pub struct Vignette {
+  /// How dark the corners get, `0.0` disables the effect, `1.0` makes them
+  /// black
+  pub intensity: Animated<f32>,
+  /// Distance from the center where the darkening starts, `0.0` is the center
+  /// and `1.0` the corners
+  pub radius: Animated<f32>,
+  /// Distance over which the darkening fades in
+  pub softness: Animated<f32>,
+  shader: Option<FullscreenPass>,
+}
+
+impl Vignette {
+  /// Softness shaders can handle, `smoothstep` needs distinct edges
+  const MIN_SOFTNESS: f32 = 0.0001;
+
+  pub fn new(
+    intensity: impl Into<Animated<f32>>,
+    radius: impl Into<Animated<f32>>,
+    softness: impl Into<Animated<f32>>,
+  ) -> Self {
+    Self {
+      intensity: intensity.into(),
+      radius: radius.into(),
+      softness: softness.into(),
+      shader: None,
+    }
+  }
+
+  fn uniform(&self, time: Time) -> VignetteUniform {
+    VignetteUniform {
+      intensity: self.intensity.evaluate(time).clamp(0.0, 1.0),
+      radius: self.radius.evaluate(time),
+      softness: self.softness.evaluate(time).max(Self::MIN_SOFTNESS),
+      _padding: 0.0,
+    }
+  }
+}
+
+impl Effect for Vignette {
+  fn apply(
+    &mut self,
+    renderer: &mut Renderer,
+    input: &RenderTarget,
+    output: &RenderTarget,
+    time: Time,
+  ) {
+    let uniform = self.uniform(time);
+    let shader = self.shader.get_or_insert_with(|| {
+      FullscreenPass::new(
+        renderer,
+        include_str!("vignette.wgsl"),
+        1,
+        std::mem::size_of::<VignetteUniform>() as u64,
+        None,
+      )
+    });
+
+    shader.render_to(renderer, output, bytemuck::bytes_of(&uniform), &[input]);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parameters_are_clamped() {
+    let uniform = Vignette::new(1.5, 0.4, 0.0).uniform(Time::default());
+    assert_eq!(uniform.intensity, 1.0);
+    assert_eq!(uniform.radius, 0.4);
+    assert_eq!(uniform.softness, Vignette::MIN_SOFTNESS);
+
+    let uniform = Vignette::new(-1.0, 0.4, 0.3).uniform(Time::default());
+    assert_eq!((uniform.intensity, uniform.softness), (0.0, 0.3));
+  }
+
+  #[test]
+  fn parameters_are_animated() {
+    let vignette = Vignette::new(
+      Animated::from_fn(|time: Time| time.clip_time as f32),
+      0.5,
+      0.2,
+    );
+    let uniform = vignette.uniform(Time::new(30, 60, 60.0));
+    assert_eq!(uniform.intensity, 0.5);
+  }
+}
@@ -0,0 +1,29 @@
+struct VignetteUniform {
+  intensity: f32,
+  radius: f32,
+  softness: f32,
+};
+
+@group(1)
+@binding(0)
+var<uniform> vignette_uniform: VignetteUniform;
+
+@group(1)
+@binding(1)
+var input_sampler: sampler;
+
+@group(1)
+@binding(2)
+var input_texture: texture_2d<f32>;
+
+@fragment
+fn fs_main(in: FullscreenOutput) -> @location(0) vec4<f32> {
+  let color = textureSample(input_texture, input_sampler, in.uv);
+
+  // 0.0 in the center, 1.0 in the corners
+  let distance = length(in.uv - vec2(0.5)) * sqrt(2.0);
+  let falloff = smoothstep(vignette_uniform.radius, vignette_uniform.radius + vignette_uniform.softness, distance);
+  let darken = 1.0 - falloff * vignette_uniform.intensity;
+
+  return vec4(color.rgb * darken, color.a);
+}
@@ -0,0 +1,231 @@
+use std::sync::MutexGuard;
+
+use wgpu::util::DeviceExt;
+
+use crate::render::{RenderTarget, Renderer};
+
+/// Runs a fragment shader over every pixel of the current render target.
+///
+/// The vertex stage is provided (see `fullscreen.wgsl`) and passes a
+/// `FullscreenOutput` with the `uv` of the pixel to `fs_main`. Bind group `1`
+/// is laid out as follows:
+///
+/// - `@binding(0)`: `var<uniform>` holding the bytes passed to
+///   [`FullscreenPass::render`], only present if `uniform_size > 0`
+/// - `@binding(1)`: linear `sampler`
+/// - `@binding(2..)`: one `texture_2d<f32>` per input texture
+#[derive(Debug)]
+pub struct FullscreenPass {
+  pipeline: wgpu::RenderPipeline,
+  bind_group_layout: wgpu::BindGroupLayout,
+  sampler: wgpu::Sampler,
+  uniform_size: u64,
+  textures: u32,
+}
+
+impl FullscreenPass {
+  pub fn new(
+    renderer: &Renderer,
+    fragment_source: &str,
+    textures: u32,
+    uniform_size: u64,
+    blend: Option<wgpu::BlendState>,
+  ) -> Self {
+    Self::with_format(
+      renderer,
+      fragment_source,
+      textures,
+      uniform_size,
+      blend,
+      renderer.wgpu_format(),
+    )
+  }
+
+  /// Like [`FullscreenPass::new`], for drawing to targets with another
+  /// `format` than the renderer's, e.g. a window
+  pub fn with_format(
+    renderer: &Renderer,
+    fragment_source: &str,
+    textures: u32,
+    uniform_size: u64,
+    blend: Option<wgpu::BlendState>,
+    format: wgpu::TextureFormat,
+  ) -> Self {
+    let device = renderer.wgpu_device();
+
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+      label: Some("Fullscreen Shader Module"),
+      source: wgpu::ShaderSource::Wgsl(
+        format!("{}\n{}", include_str!("fullscreen.wgsl"), fragment_source).into(),
+      ),
+    });
+
+    let mut entries = vec![];
+    if uniform_size > 0 {
+      entries.push(wgpu::BindGroupLayoutEntry {
+        binding: 0,
+        visibility: wgpu::ShaderStages::FRAGMENT,
+        ty: wgpu::BindingType::Buffer {
+          ty: wgpu::BufferBindingType::Uniform,
+          has_dynamic_offset: false,
+          min_binding_size: None,
+        },
+        count: None,
+      });
+    }
+    entries.push(wgpu::BindGroupLayoutEntry {
+      binding: 1,
+      visibility: wgpu::ShaderStages::FRAGMENT,
+      ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+      count: None,
+    });
+    for i in 0..textures {
+      entries.push(wgpu::BindGroupLayoutEntry {
+        binding: 2 + i,
+        visibility: wgpu::ShaderStages::FRAGMENT,
+        ty: wgpu::BindingType::Texture {
+          sample_type: wgpu::TextureSampleType::Float { filterable: true },
+          view_dimension: wgpu::TextureViewDimension::D2,
+          multisampled: false,
+        },
+        count: None,
+      });
+    }
+
+    let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+      label: Some("Fullscreen Bind Group Layout"),
+      entries: &entries,
+    });
+
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+      label: Some("Fullscreen Pipeline Layout"),
+      bind_group_layouts: &[
+        renderer.wgpu_transform_bind_group_layout(),
+        &bind_group_layout,
+      ],
+      push_constant_ranges: &[],
+    });
+
+    let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+      label: Some("Fullscreen Pipeline"),
+      layout: Some(&pipeline_layout),
+      vertex: wgpu::VertexState {
+        module: &module,
+        entry_point: Some("vs_main"),
+        buffers: &[],
+        compilation_options: wgpu::PipelineCompilationOptions::default(),
+      },
+      fragment: Some(wgpu::FragmentState {
+        module: &module,
+        entry_point: Some("fs_main"),
+        targets: &[Some(wgpu::ColorTargetState {
+          format,
+          blend,
+          write_mask: wgpu::ColorWrites::ALL,
+        })],
+        compilation_options: wgpu::PipelineCompilationOptions::default(),
+      }),
+      primitive: wgpu::PrimitiveState::default(),
+      depth_stencil: Some(wgpu::DepthStencilState {
+        format: wgpu::TextureFormat::Depth32Float,
+        depth_write_enabled: false,
+        depth_compare: wgpu::CompareFunction::Always,
+        stencil: wgpu::StencilState::default(),
+        bias: wgpu::DepthBiasState::default(),
+      }),
+      multisample: wgpu::MultisampleState::default(),
+      multiview: None,
+      cache: None,
+    });
+
+    let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+      label: Some("Fullscreen Sampler"),
+      address_mode_u: wgpu::AddressMode::ClampToEdge,
+      address_mode_v: wgpu::AddressMode::ClampToEdge,
+      mag_filter: wgpu::FilterMode::Linear,
+      min_filter: wgpu::FilterMode::Linear,
+      ..Default::default()
+    });
+
+    Self {
+      pipeline,
+      bind_group_layout,
+      sampler,
+      uniform_size,
+      textures,
+    }
+  }
+
+  pub fn render(
+    &self,
+    renderer: &Renderer,
+    mut render_pass: MutexGuard<wgpu::RenderPass<'_>>,
+    uniforms: &[u8],
+    inputs: &[&RenderTarget],
+  ) {
+    assert_eq!(
+      inputs.len(),
+      self.textures as usize,
+      "fullscreen pass expects {} input textures",
+      self.textures
+    );
+
+    let device = renderer.wgpu_device();
+
+    let uniform_buffer = (self.uniform_size > 0).then(|| {
+      assert_eq!(
+        uniforms.len() as u64,
+        self.uniform_size,
+        "fullscreen pass expects {} bytes of uniforms",
+        self.uniform_size
+      );
+
+      device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("Fullscreen Uniform Buffer"),
+        contents: uniforms,
+        usage: wgpu::BufferUsages::UNIFORM,
+      })
+    });
+
+    let mut entries = vec![];
+    if let Some(uniform_buffer) = uniform_buffer.as_ref() {
+      entries.push(wgpu::BindGroupEntry {
+        binding: 0,
+        resource: uniform_buffer.as_entire_binding(),
+      });
+    }
+    entries.push(wgpu::BindGroupEntry {
+      binding: 1,
+      resource: wgpu::BindingResource::Sampler(&self.sampler),
+    });
+    for (i, input) in inputs.iter().enumerate() {
+      entries.push(wgpu::BindGroupEntry {
+        binding: 2 + i as u32,
+        resource: wgpu::BindingResource::TextureView(input.view()),
+      });
+    }
+
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+      label: Some("Fullscreen Bind Group"),
+      layout: &self.bind_group_layout,
+      entries: &entries,
+    });
+
+    render_pass.set_pipeline(&self.pipeline);
+    render_pass.set_bind_group(1, &bind_group, &[]);
+    render_pass.draw(0..3, 0..1);
+  }
+
+  /// Runs the pass in its own render pass, replacing the contents of `output`
+  pub fn render_to(
+    &self,
+    renderer: &mut Renderer,
+    output: &RenderTarget,
+    uniforms: &[u8],
+    inputs: &[&RenderTarget],
+  ) {
+    renderer.render_to_target(output, wgpu::Color::TRANSPARENT, |renderer, pass| {
+      self.render(renderer, pass.lock().unwrap(), uniforms, inputs);
+    });
+  }
+}
@@ -0,0 +1,15 @@
+struct FullscreenOutput {
+  @builtin(position) clip_position: vec4<f32>,
+  @location(0) uv: vec2<f32>,
+};
+
+// A single triangle covering the whole target, uv (0, 0) is the top left corner
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> FullscreenOutput {
+  let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+
+  var out: FullscreenOutput;
+  out.uv = uv;
+  out.clip_position = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+  return out;
+}
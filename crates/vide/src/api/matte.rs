@@ -0,0 +1,233 @@
+use std::sync::Mutex;
+
+use bytemuck::{Pod, Zeroable};
+
+use super::fullscreen::FullscreenPass;
+use crate::{
+  clip::Clip,
+  render::{RenderTarget, Renderer, Time},
+};
+
+/// Which channel of the matte decides how much of the content is visible
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MatteMode {
+  /// Content is visible where the matte is opaque
+  #[default]
+  Alpha,
+  /// Content is visible where the matte is bright
+  Luma,
+}
+
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+struct MatteUniform {
+  mode: u32,
+  inverted: u32,
+}
+
+/// Reveals `content` through the alpha or luminance of another clip, the
+/// `matte`. Animating the matte (e.g. a [`Rect`](super::rect::Rect) moving
+/// across the screen) animates the mask shape.
+///
+/// The matte itself is never visible, it's only used as a mask. Timing follows
+/// the content clip, the matte is timed relative to the same parent sequence
+/// and counts as fully transparent outside of its own time frame.
+pub struct TrackMatte {
+  pub content: Box<dyn Clip>,
+  pub matte: Box<dyn Clip>,
+  pub mode: MatteMode,
+  pub inverted: bool,
+  resources: Option<(RenderTarget, RenderTarget, FullscreenPass)>,
+}
+
+impl TrackMatte {
+  pub fn builder() -> TrackMatteBuilder {
+    TrackMatteBuilder::default()
+  }
+
+  /// Timing of the matte while the content is at `time`, `None` outside of
+  /// the matte's time frame. The matte lives next to the content, in the
+  /// same parent sequence
+  fn matte_time(&self, time: Time) -> Option<Time> {
+    self
+      .matte
+      .in_time_frame(time.sequence_frame, time.fps)
+      .then(|| {
+        time.sibling(
+          self.matte.start(time.fps),
+          self.matte.end(time.sequence_frames, time.fps),
+        )
+      })
+  }
+
+  fn uniform(&self) -> MatteUniform {
+    MatteUniform {
+      mode: match self.mode {
+        MatteMode::Alpha => 0,
+        MatteMode::Luma => 1,
+      },
+      inverted: self.inverted as u32,
+    }
+  }
+}
+
+impl Clip for TrackMatte {
+  fn start(&self, fps: f64) -> u64 {
+    self.content.start(fps)
+  }
+
+  fn end(&self, video_end: u64, fps: f64) -> u64 {
+    self.content.end(video_end, fps)
+  }
+
+  fn in_time_frame(&self, frame: u64, fps: f64) -> bool {
+    self.content.in_time_frame(frame, fps)
+  }
+
+  fn render(&mut self, renderer: &mut Renderer, pass: &Mutex<wgpu::RenderPass<'_>>, time: Time) {
+    let matte_time = self.matte_time(time);
+    let uniform = self.uniform();
+    let (content_target, matte_target, composite) = self.resources.get_or_insert_with(|| {
+      (
+        renderer.create_render_target(),
+        renderer.create_render_target(),
+        FullscreenPass::new(
+          renderer,
+          include_str!("matte.wgsl"),
+          2,
+          std::mem::size_of::<MatteUniform>() as u64,
+          Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+        ),
+      )
+    });
+
+    renderer.render_to_target(
+      content_target,
+      wgpu::Color::TRANSPARENT,
+      |renderer, pass| {
+        self.content.render(renderer, pass, time);
+      },
+    );
+
+    renderer.render_to_target(matte_target, wgpu::Color::TRANSPARENT, |renderer, pass| {
+      if let Some(matte_time) = matte_time {
+        self.matte.render(renderer, pass, matte_time);
+      }
+    });
+
+    composite.render(
+      renderer,
+      pass.lock().unwrap(),
+      bytemuck::bytes_of(&uniform),
+      &[content_target, matte_target],
+    );
+  }
+}
+
+#[derive(Default)]
+pub struct TrackMatteBuilder {
+  content: Option<Box<dyn Clip>>,
+  matte: Option<Box<dyn Clip>>,
+  mode: MatteMode,
+  inverted: bool,
+}
+
+impl TrackMatteBuilder {
+  pub fn content(mut self, content: impl Clip + 'static) -> Self {
+    self.content = Some(Box::new(content));
+    self
+  }
+
+  pub fn matte(mut self, matte: impl Clip + 'static) -> Self {
+    self.matte = Some(Box::new(matte));
+    self
+  }
+
+  pub fn mode(mut self, mode: MatteMode) -> Self {
+    self.mode = mode;
+    self
+  }
+
+  /// Show the content where the matte is *not* opaque/bright instead
+  pub fn inverted(mut self) -> Self {
+    self.inverted = true;
+    self
+  }
+
+  pub fn build(self) -> TrackMatte {
+    TrackMatte {
+      content: self
+        .content
+        .expect("track matte is missing its content clip"),
+      matte: self.matte.expect("track matte is missing its matte clip"),
+      mode: self.mode,
+      inverted: self.inverted,
+      resources: None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::api::rect::Rect;
+
+  fn matte(content: std::ops::Range<f64>, matte: std::ops::Range<f64>) -> TrackMatteBuilder {
+    TrackMatte::builder()
+      .content(Rect::builder().timing(content).build())
+      .matte(Rect::builder().timing(matte).build())
+  }
+
+  /// Timing of the track matte at `frame` of a 100 frame parent at 10 fps
+  fn time(track_matte: &TrackMatte, frame: u64) -> Time {
+    let start = track_matte.start(10.0);
+    let end = track_matte.end(100, 10.0);
+    Time::new(frame, 100, 10.0).child(start, end)
+  }
+
+  #[test]
+  fn timing_follows_the_content() {
+    let track_matte = matte(1.0..3.0, 0.0..5.0).build();
+    assert_eq!(track_matte.start(10.0), 10);
+    assert_eq!(track_matte.end(100, 10.0), 30);
+    assert!(track_matte.in_time_frame(29, 10.0));
+    assert!(!track_matte.in_time_frame(30, 10.0));
+  }
+
+  #[test]
+  fn the_matte_is_timed_in_the_parent() {
+    let track_matte = matte(1.0..3.0, 2.0..6.0).build();
+    assert!(track_matte.matte_time(time(&track_matte, 15)).is_none());
+
+    let matte_time = track_matte.matte_time(time(&track_matte, 25)).unwrap();
+    assert_eq!(matte_time.clip_frame, 5);
+    assert_eq!(matte_time.clip_frames, 40);
+    assert_eq!(matte_time.clip_progress, 0.125);
+  }
+
+  #[test]
+  fn endless_mattes_end_with_the_parent() {
+    let track_matte = matte(1.0..3.0, 2.0..f64::INFINITY).build();
+    // 25 frames into a parent sequence that is 50 frames long
+    let parent = Time::new(25, 100, 10.0).child(0, 50);
+    let time = parent.child(track_matte.start(10.0), track_matte.end(50, 10.0));
+
+    let matte_time = track_matte.matte_time(time).unwrap();
+    assert_eq!(matte_time.clip_frame, 5);
+    assert_eq!(matte_time.clip_frames, 30);
+    assert_eq!(matte_time.clip_progress, 5.0 / 30.0);
+  }
+
+  #[test]
+  fn modes_select_the_channel() {
+    let uniform = matte(0.0..1.0, 0.0..1.0).build().uniform();
+    assert_eq!((uniform.mode, uniform.inverted), (0, 0));
+
+    let uniform = matte(0.0..1.0, 0.0..1.0)
+      .mode(MatteMode::Luma)
+      .inverted()
+      .build()
+      .uniform();
+    assert_eq!((uniform.mode, uniform.inverted), (1, 1));
+  }
+}
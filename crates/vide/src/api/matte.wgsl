@@ -0,0 +1,38 @@
+struct MatteUniform {
+  mode: u32,
+  inverted: u32,
+};
+
+@group(1)
+@binding(0)
+var<uniform> matte_uniform: MatteUniform;
+
+@group(1)
+@binding(1)
+var input_sampler: sampler;
+
+@group(1)
+@binding(2)
+var content_texture: texture_2d<f32>;
+
+@group(1)
+@binding(3)
+var matte_texture: texture_2d<f32>;
+
+@fragment
+fn fs_main(in: FullscreenOutput) -> @location(0) vec4<f32> {
+  let content = textureSample(content_texture, input_sampler, in.uv);
+  let matte = textureSample(matte_texture, input_sampler, in.uv);
+
+  // 0: Alpha, 1: Luma (Rec. 709)
+  var coverage = matte.a;
+  if (matte_uniform.mode == 1u) {
+    coverage = dot(matte.rgb, vec3(0.2126, 0.7152, 0.0722));
+  }
+
+  if (matte_uniform.inverted == 1u) {
+    coverage = 1.0 - coverage;
+  }
+
+  return content * coverage;
+}
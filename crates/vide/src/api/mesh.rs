@@ -5,6 +5,15 @@ use wgpu::util::DeviceExt;
 use super::{instance::Instance, shader::Shader, vertex::Vertex};
 use crate::render::Renderer;
 
+/// Geometry drawn with a custom shader, which needs a `vs_main` vertex and an
+/// `fs_main` fragment entry point.
+///
+/// Frames hold colors in linear light with premultiplied alpha, `blend`
+/// decides how the colors returned by `fs_main` are combined with them. Use
+/// [`wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING`] for shaders returning
+/// premultiplied colors, like the built-in ones, and
+/// [`STRAIGHT_ALPHA_BLENDING`](crate::render::STRAIGHT_ALPHA_BLENDING) for
+/// shaders returning straight alpha
 #[derive(Debug)]
 pub struct Mesh {
   _vertices: Vec<Vertex>,
@@ -26,9 +35,9 @@ impl Mesh {
     vertices: Vec<Vertex>,
     indices: Option<Vec<u16>>,
     shader: Shader,
+    blend: wgpu::BlendState,
   ) -> Self {
     let device = renderer.wgpu_device();
-    let config = renderer.wgpu_config();
 
     let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
       label: Some("Vertex Buffer"),
@@ -78,8 +87,8 @@ impl Mesh {
         module: &shader.module,
         entry_point: Some("fs_main"),
         targets: &[Some(wgpu::ColorTargetState {
-          format: config.format,
-          blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+          format: renderer.wgpu_format(),
+          blend: Some(blend),
           write_mask: wgpu::ColorWrites::ALL,
         })],
         compilation_options: wgpu::PipelineCompilationOptions::default(),
@@ -0,0 +1,192 @@
+use std::f64::consts::TAU;
+
+use super::animation::Animated;
+use crate::{lerp, render::Time};
+
+/// Shape of the noise, see [`Noise::kind`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum NoiseKind {
+  /// Smoothly blends random values, blocky compared to the others
+  Value,
+  /// Classic gradient noise
+  #[default]
+  Perlin,
+  /// Gradient noise on a triangular grid, fewer directional artifacts than
+  /// Perlin noise
+  Simplex,
+}
+
+/// Smooth pseudo-random values for organic motion like camera shake or
+/// idle movement.
+///
+/// Noise is deterministic: the same seed always produces the same values, so
+/// every render of a video looks the same. Values range from `-1.0` to `1.0`
+/// and change about once per unit of input.
+///
+/// ```
+/// # use vide::prelude::*;
+/// let noise = Noise::new(7).kind(NoiseKind::Simplex).octaves(3);
+/// let camera_x = noise.animated(2.0);
+///
+/// assert_eq!(
+///   noise.sample(1.5),
+///   Noise::new(7)
+///     .kind(NoiseKind::Simplex)
+///     .octaves(3)
+///     .sample(1.5)
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Noise {
+  seed: u64,
+  kind: NoiseKind,
+  octaves: u32,
+}
+
+impl Default for Noise {
+  fn default() -> Self {
+    Self::new(0)
+  }
+}
+
+impl Noise {
+  pub fn new(seed: u64) -> Self {
+    Self {
+      seed,
+      kind: NoiseKind::default(),
+      octaves: 1,
+    }
+  }
+
+  pub fn kind(mut self, kind: NoiseKind) -> Self {
+    self.kind = kind;
+    self
+  }
+
+  /// Layers `octaves` copies of the noise, each one twice as fast and half as
+  /// strong as the one before, adding finer detail
+  pub fn octaves(mut self, octaves: u32) -> Self {
+    self.octaves = octaves.max(1);
+    self
+  }
+
+  pub fn sample(&self, x: f64) -> f64 {
+    // Between two rows of the grid, gradient noise is always `0.0` on them
+    self.sample_2d(x, 0.5)
+  }
+
+  pub fn sample_2d(&self, x: f64, y: f64) -> f64 {
+    let (mut sum, mut total) = (0.0, 0.0);
+    let (mut amplitude, mut frequency) = (1.0, 1.0);
+    for octave in 0..self.octaves {
+      // Every octave gets its own seed, so they don't line up at the origin
+      let seed = self.seed.wrapping_add(octave as u64);
+      sum += amplitude * self.octave(seed, x * frequency, y * frequency);
+      total += amplitude;
+      amplitude /= 2.0;
+      frequency *= 2.0;
+    }
+
+    (sum / total).clamp(-1.0, 1.0)
+  }
+
+  /// Value that follows the noise over the clip's time, changing about
+  /// `frequency` times per second
+  pub fn animated(self, frequency: f64) -> Animated<f64> {
+    Animated::from_fn(move |time: Time| self.sample(time.clip_time * frequency))
+  }
+
+  fn octave(&self, seed: u64, x: f64, y: f64) -> f64 {
+    match self.kind {
+      NoiseKind::Value => value(seed, x, y),
+      NoiseKind::Perlin => perlin(seed, x, y),
+      NoiseKind::Simplex => simplex(seed, x, y),
+    }
+  }
+}
+
+fn value(seed: u64, x: f64, y: f64) -> f64 {
+  let (x0, y0) = (x.floor(), y.floor());
+  let (fx, fy) = (fade(x - x0), fade(y - y0));
+  let (i, j) = (x0 as i64, y0 as i64);
+
+  let top = lerp!(random(seed, i, j), random(seed, i + 1, j), fx);
+  let bottom = lerp!(random(seed, i, j + 1), random(seed, i + 1, j + 1), fx);
+  lerp!(top, bottom, fy)
+}
+
+fn perlin(seed: u64, x: f64, y: f64) -> f64 {
+  let (x0, y0) = (x.floor(), y.floor());
+  let (dx, dy) = (x - x0, y - y0);
+  let (i, j) = (x0 as i64, y0 as i64);
+
+  let corner = |ci: i64, cj: i64| {
+    let (gx, gy) = gradient(seed, i + ci, j + cj);
+    gx * (dx - ci as f64) + gy * (dy - cj as f64)
+  };
+
+  let top = lerp!(corner(0, 0), corner(1, 0), fade(dx));
+  let bottom = lerp!(corner(0, 1), corner(1, 1), fade(dx));
+  // Gradient noise stays within ±√½
+  lerp!(top, bottom, fade(dy)) * std::f64::consts::SQRT_2
+}
+
+fn simplex(seed: u64, x: f64, y: f64) -> f64 {
+  let skew = 0.5 * (3f64.sqrt() - 1.0);
+  let unskew = (3.0 - 3f64.sqrt()) / 6.0;
+
+  // Corner of the triangle containing the point, in skewed space
+  let s = (x + y) * skew;
+  let (i, j) = ((x + s).floor(), (y + s).floor());
+  let t = (i + j) * unskew;
+  let (x0, y0) = (x - (i - t), y - (j - t));
+  let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+
+  let corners = [
+    (0, 0, x0, y0),
+    (i1, j1, x0 - i1 as f64 + unskew, y0 - j1 as f64 + unskew),
+    (1, 1, x0 - 1.0 + 2.0 * unskew, y0 - 1.0 + 2.0 * unskew),
+  ];
+
+  let sum: f64 = corners
+    .into_iter()
+    .map(|(ci, cj, dx, dy)| {
+      let falloff = 0.5 - dx * dx - dy * dy;
+      if falloff <= 0.0 {
+        return 0.0;
+      }
+
+      let (gx, gy) = gradient(seed, i as i64 + ci, j as i64 + cj);
+      falloff.powi(4) * (gx * dx + gy * dy)
+    })
+    .sum();
+
+  // Scales the sum of the three corners to roughly ±1
+  sum * 99.0
+}
+
+/// Random unit vector at a grid point
+fn gradient(seed: u64, x: i64, y: i64) -> (f64, f64) {
+  let angle = (random(seed, x, y) + 1.0) * 0.5 * TAU;
+  (angle.cos(), angle.sin())
+}
+
+/// Random value from `-1.0` to `1.0` at a grid point
+fn random(seed: u64, x: i64, y: i64) -> f64 {
+  let hash = mix(seed ^ mix(x as u64 ^ mix(y as u64 ^ 0x9e37_79b9_7f4a_7c15)));
+  (hash >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+}
+
+/// SplitMix64 finalizer, spreads every input bit across the output
+fn mix(mut z: u64) -> u64 {
+  z = z.wrapping_add(0x9e37_79b9_7f4a_7c15);
+  z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+  z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+  z ^ (z >> 31)
+}
+
+/// Quintic smoothstep, its first and second derivative are `0.0` at the grid
+/// points so there are no visible seams
+fn fade(t: f64) -> f64 {
+  t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
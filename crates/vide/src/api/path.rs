@@ -0,0 +1,270 @@
+use super::animation::Animated;
+
+/// A curve objects can travel along, see [`Path::follow`].
+///
+/// Curves are flattened into short line segments, positions along the path
+/// are measured by arc length, so objects move at a constant speed when the
+/// progress changes linearly.
+#[derive(Debug, Clone)]
+pub struct Path {
+  points: Vec<(f32, f32)>,
+  /// Distance from the start of the path to every point
+  distances: Vec<f32>,
+}
+
+impl Path {
+  /// Line segments each bezier curve is split into
+  const CURVE_SEGMENTS: usize = 32;
+
+  pub fn builder() -> PathBuilder {
+    PathBuilder::default()
+  }
+
+  /// Creates a path going through every point in a straight line
+  pub fn polyline(points: impl IntoIterator<Item = (f32, f32)>) -> Self {
+    let mut points = points.into_iter();
+    let mut builder = Self::builder().start_at(points.next().unwrap_or((0.0, 0.0)));
+    for point in points {
+      builder = builder.line_to(point);
+    }
+
+    builder.build()
+  }
+
+  /// Total length of the path
+  pub fn length(&self) -> f32 {
+    self.distances.last().copied().unwrap_or(0.0)
+  }
+
+  /// Point at `progress` of the path's length, ranging from `0.0` to `1.0`
+  pub fn point_at(&self, progress: f64) -> (f32, f32) {
+    match self.locate(progress) {
+      Some((index, t)) => {
+        let (start, end) = (self.points[index - 1], self.points[index]);
+        (
+          start.0 + (end.0 - start.0) * t,
+          start.1 + (end.1 - start.1) * t,
+        )
+      }
+      None => self.points.first().copied().unwrap_or((0.0, 0.0)),
+    }
+  }
+
+  /// Direction the path is heading in at `progress`, as a counter-clockwise
+  /// angle in degrees where `0.0` points to the right
+  pub fn angle_at(&self, progress: f64) -> f32 {
+    match self.locate(progress) {
+      Some((index, _)) => {
+        let (start, end) = (self.points[index - 1], self.points[index]);
+        (end.1 - start.1).atan2(end.0 - start.0).to_degrees()
+      }
+      None => 0.0,
+    }
+  }
+
+  /// Position that travels along the path as `progress` goes from `0.0` to
+  /// `1.0`, e.g. keyframed with [`Animated::builder`]
+  pub fn follow(&self, progress: impl Into<Animated<f64>>) -> Animated<(f32, f32)> {
+    let path = self.clone();
+    let progress = progress.into();
+    Animated::from_fn(move |time| path.point_at(progress.evaluate(time)))
+  }
+
+  /// Rotation in degrees that keeps an object following the path with the
+  /// same `progress` aligned to it
+  pub fn orient(&self, progress: impl Into<Animated<f64>>) -> Animated<f32> {
+    let path = self.clone();
+    let progress = progress.into();
+    Animated::from_fn(move |time| path.angle_at(progress.evaluate(time)))
+  }
+
+  /// Index of the point ending the segment at `progress` and how far along
+  /// that segment it is
+  fn locate(&self, progress: f64) -> Option<(usize, f32)> {
+    if self.points.len() < 2 {
+      return None;
+    }
+
+    let distance = progress.clamp(0.0, 1.0) as f32 * self.length();
+    let index = self
+      .distances
+      .partition_point(|d| *d < distance)
+      .clamp(1, self.points.len() - 1);
+
+    let start = self.distances[index - 1];
+    let length = self.distances[index] - start;
+    let t = if length > 0.0 {
+      (distance - start) / length
+    } else {
+      0.0
+    };
+
+    Some((index, t))
+  }
+}
+
+#[derive(Default)]
+pub struct PathBuilder {
+  points: Vec<(f32, f32)>,
+}
+
+impl PathBuilder {
+  /// Starts the path at `point`, otherwise it starts at `(0.0, 0.0)`
+  pub fn start_at(mut self, point: (f32, f32)) -> Self {
+    self.points.clear();
+    self.points.push(point);
+    self
+  }
+
+  pub fn line_to(mut self, point: (f32, f32)) -> Self {
+    self.push(point);
+    self
+  }
+
+  pub fn quadratic_to(mut self, control: (f32, f32), point: (f32, f32)) -> Self {
+    let start = self.last();
+    for i in 1..=Path::CURVE_SEGMENTS {
+      let t = i as f32 / Path::CURVE_SEGMENTS as f32;
+      let u = 1.0 - t;
+      self.push((
+        u * u * start.0 + 2.0 * u * t * control.0 + t * t * point.0,
+        u * u * start.1 + 2.0 * u * t * control.1 + t * t * point.1,
+      ));
+    }
+    self
+  }
+
+  pub fn cubic_to(
+    mut self,
+    control_1: (f32, f32),
+    control_2: (f32, f32),
+    point: (f32, f32),
+  ) -> Self {
+    let start = self.last();
+    for i in 1..=Path::CURVE_SEGMENTS {
+      let t = i as f32 / Path::CURVE_SEGMENTS as f32;
+      let u = 1.0 - t;
+      let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+      self.push((
+        a * start.0 + b * control_1.0 + c * control_2.0 + d * point.0,
+        a * start.1 + b * control_1.1 + c * control_2.1 + d * point.1,
+      ));
+    }
+    self
+  }
+
+  /// Goes back to the start of the path in a straight line
+  pub fn close(mut self) -> Self {
+    let start = self.points.first().copied().unwrap_or((0.0, 0.0));
+    self.push(start);
+    self
+  }
+
+  pub fn build(self) -> Path {
+    let mut points = self.points;
+    if points.is_empty() {
+      points.push((0.0, 0.0));
+    }
+
+    let mut total = 0.0;
+    let distances = points
+      .iter()
+      .enumerate()
+      .map(|(i, point)| {
+        if let Some(previous) = i.checked_sub(1).map(|i| points[i]) {
+          total += (point.0 - previous.0).hypot(point.1 - previous.1);
+        }
+        total
+      })
+      .collect();
+
+    Path { points, distances }
+  }
+
+  fn last(&self) -> (f32, f32) {
+    self.points.last().copied().unwrap_or((0.0, 0.0))
+  }
+
+  /// Adds a point, skipping duplicates since they have no direction
+  fn push(&mut self, point: (f32, f32)) {
+    if self.points.is_empty() {
+      self.points.push((0.0, 0.0));
+    }
+
+    if self.last() != point {
+      self.points.push(point);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn assert_near(actual: (f32, f32), expected: (f32, f32)) {
+    assert!(
+      (actual.0 - expected.0).abs() < 1e-4 && (actual.1 - expected.1).abs() < 1e-4,
+      "{actual:?} != {expected:?}",
+    );
+  }
+
+  #[test]
+  fn endpoints_are_at_the_ends_of_the_progress() {
+    let path = Path::polyline([(1.0, 2.0), (4.0, 6.0), (10.0, 6.0)]);
+    assert_eq!(path.length(), 11.0);
+    assert_near(path.point_at(0.0), (1.0, 2.0));
+    assert_near(path.point_at(1.0), (10.0, 6.0));
+    // Progress outside of the path is clamped to its ends
+    assert_near(path.point_at(-1.0), (1.0, 2.0));
+    assert_near(path.point_at(2.0), (10.0, 6.0));
+  }
+
+  #[test]
+  fn straight_segments_are_split_evenly() {
+    let path = Path::polyline([(0.0, 0.0), (10.0, 0.0)]);
+    assert_near(path.point_at(0.5), (5.0, 0.0));
+    assert_near(path.point_at(0.25), (2.5, 0.0));
+  }
+
+  #[test]
+  fn progress_is_spaced_by_length_across_segments() {
+    // 2 units right, then 6 units up
+    let path = Path::polyline([(0.0, 0.0), (2.0, 0.0), (2.0, 6.0)]);
+    assert_near(path.point_at(0.125), (1.0, 0.0));
+    assert_near(path.point_at(0.25), (2.0, 0.0));
+    assert_near(path.point_at(0.5), (2.0, 2.0));
+    assert_near(path.point_at(0.75), (2.0, 4.0));
+  }
+
+  #[test]
+  fn angles_follow_the_direction() {
+    let line = Path::polyline([(0.0, 0.0), (10.0, 10.0), (10.0, 0.0)]);
+    assert!((line.angle_at(0.25) - 45.0).abs() < 1e-4);
+    assert!((line.angle_at(0.75) + 90.0).abs() < 1e-4);
+
+    // Leaves towards the control point and arrives from it
+    let curve = Path::builder()
+      .quadratic_to((5.0, 10.0), (10.0, 0.0))
+      .build();
+    let tangent = 2f32.atan().to_degrees();
+    assert!((curve.angle_at(0.0) - tangent).abs() < 3.0);
+    assert!((curve.angle_at(1.0) + tangent).abs() < 3.0);
+    assert_near(curve.point_at(0.5), (5.0, 5.0));
+  }
+
+  #[test]
+  fn paths_without_segments_stay_put() {
+    let empty = Path::builder().build();
+    assert_eq!(empty.locate(0.5), None);
+    assert_eq!(empty.point_at(0.5), (0.0, 0.0));
+    assert_eq!(empty.angle_at(0.5), 0.0);
+
+    let point = Path::builder()
+      .start_at((3.0, 4.0))
+      .line_to((3.0, 4.0))
+      .build();
+    assert_eq!(point.length(), 0.0);
+    assert_eq!(point.locate(1.0), None);
+    assert_eq!(point.point_at(1.0), (3.0, 4.0));
+  }
+}
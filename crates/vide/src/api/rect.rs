@@ -1,14 +1,20 @@
-use std::sync::MutexGuard;
+use std::sync::Mutex;
 
 use super::{
-  animation::Animated, color::Color, instance::Instance, mesh::Mesh, shader::Shader,
-  transform::OPENGL_TO_WGPU_MATRIX, vertex::Vertex,
+  animation::Animated, choreography::Placed, color::Color, instance::Instance, mesh::Mesh,
+  path::Path, shader::Shader, transform::OPENGL_TO_WGPU_MATRIX, vertex::Vertex,
+};
+use crate::{
+  clip::{Clip, IntoFrame},
+  render::{Renderer, Time},
+  unanimated,
 };
-use crate::{clip::Clip, render::Renderer, unanimated};
 
 pub struct Rect {
   pub position: Animated<(f32, f32)>,
   pub size: Animated<(f32, f32)>,
+  /// Counter-clockwise rotation around the center, in degrees
+  pub rotation: Animated<f32>,
   pub color: Animated<Color>,
   pub radius: Animated<f32>,
   pub start: f64,
@@ -22,20 +28,20 @@ impl Rect {
 }
 
 impl Clip for Rect {
-  fn start(&self) -> u64 {
-    (self.start * 60.0) as u64
+  fn start(&self, fps: f64) -> u64 {
+    self.start.into_frame(fps)
   }
 
-  fn end(&self, video_end: u64) -> u64 {
+  fn end(&self, video_end: u64, fps: f64) -> u64 {
     if self.end.is_infinite() {
       video_end
     } else {
-      (self.end * 60.0) as u64
+      self.end.into_frame(fps)
     }
   }
 
-  fn in_time_frame(&self, frame: u64) -> bool {
-    let start_frame = self.start();
+  fn in_time_frame(&self, frame: u64, fps: f64) -> bool {
+    let start_frame = self.start(fps);
     if frame < start_frame {
       return false;
     }
@@ -44,19 +50,15 @@ impl Clip for Rect {
       return true;
     }
 
-    frame < (self.end * 60.0) as u64
+    frame < self.end.into_frame(fps)
   }
 
-  fn render(
-    &mut self,
-    renderer: &mut Renderer,
-    pass: MutexGuard<wgpu::RenderPass<'_>>,
-    frame: u64,
-  ) {
-    let position = self.position.evaluate(frame);
-    let size = self.size.evaluate(frame);
-    let color = self.color.evaluate(frame);
-    let radius = self.radius.evaluate(frame);
+  fn render(&mut self, renderer: &mut Renderer, pass: &Mutex<wgpu::RenderPass<'_>>, time: Time) {
+    let position = self.position.evaluate(time);
+    let size = self.size.evaluate(time);
+    let rotation = self.rotation.evaluate(time);
+    let color = self.color.evaluate(time);
+    let radius = self.radius.evaluate(time);
 
     let shader = Shader::new(renderer, include_str!("rect.wgsl").into());
     let mut mesh = Mesh::new(
@@ -81,12 +83,15 @@ impl Clip for Rect {
       ],
       Some(vec![0, 1, 2, 2, 1, 3]),
       shader,
+      // rect.wgsl returns premultiplied colors
+      wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING,
     );
 
     let instance = Instance {
       matrix: (cgmath::Matrix4::from_translation(cgmath::Vector3::new(
         position.0, position.1, 0.0,
-      )) * cgmath::Matrix4::from_nonuniform_scale(size.0, size.1, 1.0)
+      )) * cgmath::Matrix4::from_angle_z(cgmath::Deg(rotation))
+        * cgmath::Matrix4::from_nonuniform_scale(size.0, size.1, 1.0)
         * OPENGL_TO_WGPU_MATRIX)
         .into(),
       color: color.into(),
@@ -94,7 +99,7 @@ impl Clip for Rect {
     };
 
     mesh.render(
-      pass,
+      pass.lock().unwrap(),
       renderer.wgpu_device(),
       renderer.wgpu_queue(),
       vec![instance],
@@ -105,6 +110,7 @@ impl Clip for Rect {
 pub struct RectBuilder {
   position: Option<Animated<(f32, f32)>>,
   size: Option<Animated<(f32, f32)>>,
+  rotation: Option<Animated<f32>>,
   color: Option<Animated<Color>>,
   radius: Option<Animated<f32>>,
   start: f64,
@@ -116,6 +122,7 @@ impl Default for RectBuilder {
     Self {
       position: None,
       size: None,
+      rotation: None,
       color: None,
       radius: None,
       start: 0.0,
@@ -135,6 +142,28 @@ impl RectBuilder {
     self
   }
 
+  /// Counter-clockwise rotation around the center, in degrees
+  pub fn rotation(mut self, rotation: impl Into<Animated<f32>>) -> Self {
+    self.rotation = Some(rotation.into());
+    self
+  }
+
+  /// Moves the rect along `path` as `progress` goes from `0.0` to `1.0`. With
+  /// `orient`, the rect is rotated to follow the direction of the path
+  pub fn motion_path(
+    mut self,
+    path: &Path,
+    progress: impl Into<Animated<f64>>,
+    orient: bool,
+  ) -> Self {
+    let progress = progress.into();
+    if orient {
+      self.rotation = Some(path.orient(progress.clone()));
+    }
+    self.position = Some(path.follow(progress));
+    self
+  }
+
   pub fn color(mut self, color: impl Into<Animated<Color>>) -> Self {
     self.color = Some(color.into());
     self
@@ -156,6 +185,7 @@ impl RectBuilder {
     Rect {
       position: self.position.unwrap_or_else(|| unanimated!((0.0, 0.0))),
       size: self.size.unwrap_or_else(|| unanimated!((100.0, 100.0))),
+      rotation: self.rotation.unwrap_or_else(|| unanimated!(0.0f32)),
       color: self.color.unwrap_or_else(|| unanimated!(Color::WHITE)),
       radius: self.radius.unwrap_or_else(|| unanimated!(0.0)),
       start: self.start,
@@ -163,3 +193,13 @@ impl RectBuilder {
     }
   }
 }
+
+impl Placed for RectBuilder {
+  fn placement(&self) -> std::ops::Range<f64> {
+    self.start..self.end
+  }
+
+  fn placement_mut(&mut self) -> (&mut f64, &mut f64) {
+    (&mut self.start, &mut self.end)
+  }
+}
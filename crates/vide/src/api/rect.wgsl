@@ -59,5 +59,7 @@ let center = vec2(0.5);
   let d = length(max(abs(position) - half_size, vec2(0.0))) - in.radius / 2.0;
   let alpha = 1.0 - smoothstep(0.0, fwidth(d), d);
 
-  return vec4(in.color.rgb, in.color.a * alpha);
+  // Premultiplied, like everything that is rendered
+  let coverage = in.color.a * alpha;
+  return vec4(in.color.rgb * coverage, coverage);
 }
@@ -0,0 +1,348 @@
+use std::{collections::VecDeque, sync::Mutex};
+
+use super::{
+  choreography::Placed,
+  effect::{Effect, EffectStack},
+};
+use crate::{
+  clip::{Clip, IntoFrame},
+  render::{progress, Renderer, Time},
+};
+
+/// A clip containing other clips on its own local timeline, like a
+/// composition. Sequences can be nested arbitrarily.
+///
+/// The local timeline starts at `0.0`, clips inside of it are timed relative
+/// to it. Placed in its parent at `start..end`, the sequence plays its local
+/// timeline starting at `trim`, `speed` times as fast.
+///
+/// Clips inside the sequence see it as their parent sequence, e.g.
+/// [`Time::sequence_frame`] is the current frame of the local timeline.
+pub struct Sequence {
+  clips: VecDeque<Box<dyn Clip>>,
+  effects: EffectStack,
+  /// Start in the parent, in seconds
+  pub start: f64,
+  /// End in the parent, in seconds. When infinite, the sequence plays until
+  /// its local timeline ends, or until its parent ends if `duration` is
+  /// infinite as well
+  pub end: f64,
+  /// Length of the local timeline, in seconds
+  pub duration: f64,
+  /// Point of the local timeline the sequence starts playing at, in seconds
+  pub trim: f64,
+  /// Playback speed of the local timeline, `2.0` plays twice as fast
+  pub speed: f64,
+}
+
+impl Sequence {
+  pub fn builder() -> SequenceBuilder {
+    SequenceBuilder::default()
+  }
+
+  #[inline]
+  pub fn clips(&self) -> &VecDeque<Box<dyn Clip>> {
+    &self.clips
+  }
+
+  #[inline]
+  pub fn clips_mut(&mut self) -> &mut VecDeque<Box<dyn Clip>> {
+    &mut self.clips
+  }
+
+  #[inline]
+  pub fn push_clip(&mut self, clip: impl Clip + 'static) {
+    self.clips.push_front(Box::new(clip));
+  }
+
+  #[inline]
+  pub fn push_effect(&mut self, effect: impl Effect + 'static) {
+    self.effects.push(effect);
+  }
+
+  /// End of the local timeline when the sequence is `frames` frames long in
+  /// its parent, clips with an infinite end last until here
+  fn local_end(&self, frames: u64, fps: f64) -> u64 {
+    if self.duration.is_infinite() {
+      self.local_frame(frames, fps)
+    } else {
+      self.duration.into_frame(fps)
+    }
+  }
+
+  /// Frame of the local timeline shown `frame` frames after the start of the
+  /// sequence
+  fn local_frame(&self, frame: u64, fps: f64) -> u64 {
+    self.trim.into_frame(fps) + (frame as f64 * self.speed) as u64
+  }
+
+  /// Describes the sequence by its local timeline, so that it becomes the
+  /// parent sequence of the clips inside of it. `None` once the local
+  /// timeline is over
+  fn local_time(&self, time: Time) -> Option<Time> {
+    let local_end = self.local_end(time.clip_frames, time.fps);
+    let local_frame = self.local_frame(time.clip_frame, time.fps);
+    if local_frame >= local_end {
+      return None;
+    }
+
+    let mut local_time = time;
+    local_time.clip_frame = local_frame;
+    local_time.clip_time = local_frame as f64 / time.fps;
+    local_time.clip_progress = progress(local_frame, local_end);
+    local_time.clip_frames = local_end;
+
+    Some(local_time)
+  }
+
+  /// Renders the clips visible at `local_time` of the local timeline
+  fn render_clips(
+    clips: &mut VecDeque<Box<dyn Clip>>,
+    renderer: &mut Renderer,
+    pass: &Mutex<wgpu::RenderPass<'_>>,
+    local_time: Time,
+  ) {
+    for clip in clips.iter_mut() {
+      if clip.in_time_frame(local_time.clip_frame, local_time.fps) {
+        let clip_time = local_time.child(
+          clip.start(local_time.fps),
+          clip.end(local_time.clip_frames, local_time.fps),
+        );
+        clip.render(renderer, pass, clip_time);
+      }
+    }
+  }
+}
+
+impl Clip for Sequence {
+  fn start(&self, fps: f64) -> u64 {
+    self.start.into_frame(fps)
+  }
+
+  fn end(&self, video_end: u64, fps: f64) -> u64 {
+    if !self.end.is_infinite() {
+      self.end.into_frame(fps)
+    } else if !self.duration.is_infinite() {
+      let remaining = (self.duration - self.trim).max(0.0) / self.speed;
+      self.start(fps) + remaining.into_frame(fps)
+    } else {
+      video_end
+    }
+  }
+
+  fn in_time_frame(&self, frame: u64, fps: f64) -> bool {
+    frame >= self.start(fps) && frame < self.end(u64::MAX, fps)
+  }
+
+  fn render(&mut self, renderer: &mut Renderer, pass: &Mutex<wgpu::RenderPass<'_>>, time: Time) {
+    // An explicit end can outlast the local timeline, nothing is left to show
+    let Some(local_time) = self.local_time(time) else {
+      return;
+    };
+
+    let clips = &mut self.clips;
+    if self.effects.is_empty() {
+      // Nothing to post-process, the clips can draw straight into the parent
+      Self::render_clips(clips, renderer, pass, local_time);
+    } else {
+      self.effects.render(
+        renderer,
+        pass.lock().unwrap(),
+        local_time,
+        wgpu::Color::TRANSPARENT,
+        |renderer, pass| Self::render_clips(clips, renderer, pass, local_time),
+      );
+    }
+  }
+}
+
+pub struct SequenceBuilder {
+  clips: VecDeque<Box<dyn Clip>>,
+  effects: EffectStack,
+  start: f64,
+  end: f64,
+  duration: f64,
+  trim: f64,
+  speed: f64,
+}
+
+impl Default for SequenceBuilder {
+  fn default() -> Self {
+    Self {
+      clips: VecDeque::new(),
+      effects: EffectStack::new(),
+      start: 0.0,
+      end: f64::INFINITY,
+      duration: f64::INFINITY,
+      trim: 0.0,
+      speed: 1.0,
+    }
+  }
+}
+
+impl SequenceBuilder {
+  /// Clips added first are drawn on top, like
+  /// [`Video::push_clip`](super::video::Video::push_clip)
+  pub fn clip(mut self, clip: impl Clip + 'static) -> Self {
+    self.clips.push_front(Box::new(clip));
+    self
+  }
+
+  /// Effects are applied to everything inside the sequence, in the order they
+  /// are added
+  pub fn effect(mut self, effect: impl Effect + 'static) -> Self {
+    self.effects.push(effect);
+    self
+  }
+
+  /// Placement in the parent, in seconds
+  pub fn timing(mut self, range: impl Into<std::ops::Range<f64>>) -> Self {
+    let range = range.into();
+    self.start = range.start;
+    self.end = range.end;
+    self
+  }
+
+  /// Length of the local timeline, in seconds
+  pub fn duration(mut self, duration: f64) -> Self {
+    self.duration = duration;
+    self
+  }
+
+  /// Skips the first `trim` seconds of the local timeline
+  pub fn trim(mut self, trim: f64) -> Self {
+    self.trim = trim;
+    self
+  }
+
+  /// Plays the local timeline `speed` times as fast, must be positive
+  pub fn speed(mut self, speed: f64) -> Self {
+    assert!(
+      speed > 0.0,
+      "sequence speed has to be positive, got {}",
+      speed
+    );
+    self.speed = speed;
+    self
+  }
+
+  pub fn build(self) -> Sequence {
+    Sequence {
+      clips: self.clips,
+      effects: self.effects,
+      start: self.start,
+      end: self.end,
+      duration: self.duration,
+      trim: self.trim,
+      speed: self.speed,
+    }
+  }
+}
+
+impl Placed for SequenceBuilder {
+  fn placement(&self) -> std::ops::Range<f64> {
+    // Without an end, the sequence ends with its local timeline
+    if self.end.is_infinite() && !self.duration.is_infinite() {
+      self.start..self.start + (self.duration - self.trim).max(0.0) / self.speed
+    } else {
+      self.start..self.end
+    }
+  }
+
+  fn placement_mut(&mut self) -> (&mut f64, &mut f64) {
+    (&mut self.start, &mut self.end)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::api::choreography::Timed;
+
+  const FPS: f64 = 10.0;
+
+  /// Timing of the sequence's local timeline `frame` frames after its start,
+  /// when it is placed in a 100 frame video
+  fn local_time(sequence: &Sequence, frame: u64) -> Option<Time> {
+    let (start, end) = (sequence.start(FPS), sequence.end(100, FPS));
+    sequence.local_time(Time::new(start + frame, 100, FPS).child(start, end))
+  }
+
+  fn local_frames(sequence: &Sequence, frame: u64) -> Option<(u64, u64)> {
+    local_time(sequence, frame).map(|time| (time.clip_frame, time.clip_frames))
+  }
+
+  #[test]
+  fn trim_skips_the_start_of_the_local_timeline() {
+    let sequence = Sequence::builder()
+      .timing(1.0..3.0)
+      .duration(4.0)
+      .trim(1.5)
+      .build();
+
+    assert_eq!(local_frames(&sequence, 0), Some((15, 40)));
+    assert_eq!(local_frames(&sequence, 10), Some((25, 40)));
+  }
+
+  #[test]
+  fn speed_scales_the_local_timeline() {
+    let sequence = Sequence::builder()
+      .timing(0.0..2.0)
+      .duration(4.0)
+      .speed(2.0)
+      .build();
+
+    assert_eq!(local_frames(&sequence, 5), Some((10, 40)));
+    let time = local_time(&sequence, 15).unwrap();
+    assert_eq!(time.clip_frame, 30);
+    assert_eq!(time.clip_progress, 0.75);
+  }
+
+  #[test]
+  fn the_duration_sets_the_end() {
+    let builder = Sequence::builder()
+      .timing(1.0..f64::INFINITY)
+      .duration(4.0)
+      .trim(1.0)
+      .speed(2.0);
+    assert_eq!(builder.span(), 1.0..2.5);
+
+    let sequence = builder.build();
+    assert_eq!(sequence.end(100, FPS), 25);
+    assert!(sequence.in_time_frame(24, FPS));
+    assert!(!sequence.in_time_frame(25, FPS));
+  }
+
+  #[test]
+  fn nothing_is_shown_past_the_duration() {
+    let sequence = Sequence::builder().timing(0.0..5.0).duration(2.0).build();
+
+    assert_eq!(local_frames(&sequence, 19), Some((19, 20)));
+    assert_eq!(local_time(&sequence, 19).unwrap().clip_progress, 0.95);
+    assert!(local_time(&sequence, 20).is_none());
+    assert!(local_time(&sequence, 45).is_none());
+  }
+
+  #[test]
+  fn unbounded_sequences_end_with_their_parent() {
+    let sequence = Sequence::builder().timing(2.0..f64::INFINITY).build();
+    assert_eq!(sequence.end(100, FPS), 100);
+
+    let time = local_time(&sequence, 40).unwrap();
+    assert_eq!((time.clip_frame, time.clip_frames), (40, 80));
+    assert_eq!(time.clip_progress, 0.5);
+
+    // Clips with an infinite end are placed until the end of the parent
+    let child = time.child(20, sequence.end(time.clip_frames, FPS));
+    assert_eq!(child.clip_progress, 20.0 / 60.0);
+  }
+
+  #[test]
+  fn delays_move_the_whole_placement() {
+    let builder = Sequence::builder().timing(1.0..2.0).delay(0.5);
+    assert_eq!(builder.span(), 1.5..2.5);
+
+    let builder = Sequence::builder().duration(2.0).delay(1.0);
+    assert_eq!(builder.span(), 1.0..3.0);
+  }
+}
@@ -0,0 +1,377 @@
+use std::sync::Mutex;
+
+use super::{animation::Animated, choreography::Placed, color::Color, fullscreen::FullscreenPass};
+use crate::{
+  clip::{Clip, IntoFrame},
+  render::{Renderer, Time, STRAIGHT_ALPHA_BLENDING},
+};
+
+/// A user defined parameter of a [`ShaderClip`]
+pub enum Parameter {
+  /// Available as `f32` in the shader
+  Float(Animated<f32>),
+  /// Available as `vec4<f32>` in the shader
+  Color(Animated<Color>),
+}
+
+impl Parameter {
+  /// Alignment and size in the uniform buffer, following the WGSL layout rules
+  fn layout(&self) -> (usize, usize) {
+    match self {
+      Self::Float(_) => (4, 4),
+      Self::Color(_) => (16, 16),
+    }
+  }
+
+  fn wgsl_type(&self) -> &'static str {
+    match self {
+      Self::Float(_) => "f32",
+      Self::Color(_) => "vec4<f32>",
+    }
+  }
+}
+
+/// Runs a custom WGSL fragment shader over the whole screen, e.g. for
+/// procedural backgrounds.
+///
+/// The source has to define `fn fs_main(in: FullscreenOutput) -> @location(0)
+/// vec4<f32>`, where `in.uv` ranges from `(0, 0)` in the top left to `(1, 1)`
+/// in the bottom right corner. The returned color is in linear light with
+/// straight alpha, and is alpha blended with everything behind the clip. A
+/// `uniforms` variable is declared for you with the following fields:
+///
+/// - `resolution: vec2<f32>`: output resolution in pixels
+/// - `time: f32`: seconds since the start of the clip
+/// - `progress: f32`: clip progress, ranging from `0.0` to `1.0`
+/// - `video_time: f32`: seconds since the start of the video
+/// - `frame: u32`: frames since the start of the clip
+///
+/// Followed by every parameter added with [`ShaderClipBuilder::float`] and
+/// [`ShaderClipBuilder::color`], under the name they were added with. Names
+/// have to be WGSL identifiers that aren't keywords or one of the fields
+/// above.
+///
+/// ```
+/// # use vide::prelude::*;
+/// let clip = ShaderClip::builder()
+///   .source(
+///     "@fragment
+///     fn fs_main(in: FullscreenOutput) -> @location(0) vec4<f32> {
+///       let wave = sin(in.uv.x * 10.0 + uniforms.time * uniforms.speed) * 0.5 + 0.5;
+///       return vec4(uniforms.tint.rgb * wave, 1.0);
+///     }",
+///   )
+///   .float("speed", 2.0)
+///   .color("tint", rgb8!(0xda, 0x00, 0x37))
+///   .build();
+/// ```
+pub struct ShaderClip {
+  pub source: String,
+  pub parameters: Vec<(String, Parameter)>,
+  pub start: f64,
+  pub end: f64,
+  shader: Option<FullscreenPass>,
+}
+
+impl ShaderClip {
+  /// Names of the fields every shader clip gets
+  const BUILTIN_FIELDS: [&'static str; 5] =
+    ["resolution", "time", "progress", "video_time", "frame"];
+  /// Size of the fields every shader clip gets, before the user parameters
+  const BUILTIN_SIZE: usize = 24;
+
+  pub fn builder() -> ShaderClipBuilder {
+    ShaderClipBuilder::default()
+  }
+
+  /// Offsets of the parameters in the uniform buffer and its total size
+  fn layout(&self) -> (Vec<usize>, usize) {
+    let mut offset = Self::BUILTIN_SIZE;
+    let mut max_align = 16;
+    let offsets = self
+      .parameters
+      .iter()
+      .map(|(_, parameter)| {
+        let (align, size) = parameter.layout();
+        max_align = max_align.max(align);
+        let aligned = offset.next_multiple_of(align);
+        offset = aligned + size;
+        aligned
+      })
+      .collect();
+
+    (offsets, offset.next_multiple_of(max_align))
+  }
+
+  fn wgsl(&self) -> String {
+    let parameters = self
+      .parameters
+      .iter()
+      .map(|(name, parameter)| format!("  {}: {},\n", name, parameter.wgsl_type()))
+      .collect::<String>();
+
+    format!(
+      "struct ShaderClipUniform {{
+  resolution: vec2<f32>,
+  time: f32,
+  progress: f32,
+  video_time: f32,
+  frame: u32,
+{parameters}}};
+
+@group(1)
+@binding(0)
+var<uniform> uniforms: ShaderClipUniform;
+
+{}",
+      self.source
+    )
+  }
+
+  fn uniforms(&self, renderer: &Renderer, time: Time) -> Vec<u8> {
+    let (offsets, size) = self.layout();
+    let mut data = vec![0u8; size];
+
+    let mut write = |offset: usize, bytes: &[u8]| {
+      data[offset..offset + bytes.len()].copy_from_slice(bytes);
+    };
+
+    let (width, height) = renderer.settings.resolution;
+    write(0, bytemuck::bytes_of(&[width as f32, height as f32]));
+    write(8, bytemuck::bytes_of(&(time.clip_time as f32)));
+    write(12, bytemuck::bytes_of(&(time.clip_progress as f32)));
+    write(16, bytemuck::bytes_of(&(time.video_time as f32)));
+    write(20, bytemuck::bytes_of(&(time.clip_frame as u32)));
+
+    for ((_, parameter), offset) in self.parameters.iter().zip(offsets) {
+      match parameter {
+        Parameter::Float(value) => write(offset, bytemuck::bytes_of(&value.evaluate(time))),
+        Parameter::Color(value) => write(
+          offset,
+          bytemuck::bytes_of(&Into::<[f32; 4]>::into(value.evaluate(time))),
+        ),
+      }
+    }
+
+    data
+  }
+}
+
+impl Clip for ShaderClip {
+  fn start(&self, fps: f64) -> u64 {
+    self.start.into_frame(fps)
+  }
+
+  fn end(&self, video_end: u64, fps: f64) -> u64 {
+    if self.end.is_infinite() {
+      video_end
+    } else {
+      self.end.into_frame(fps)
+    }
+  }
+
+  fn in_time_frame(&self, frame: u64, fps: f64) -> bool {
+    let start_frame = self.start(fps);
+    if frame < start_frame {
+      return false;
+    }
+
+    if self.end.is_infinite() {
+      return true;
+    }
+
+    frame < self.end.into_frame(fps)
+  }
+
+  fn render(&mut self, renderer: &mut Renderer, pass: &Mutex<wgpu::RenderPass<'_>>, time: Time) {
+    let uniforms = self.uniforms(renderer, time);
+
+    if self.shader.is_none() {
+      self.shader = Some(FullscreenPass::new(
+        renderer,
+        &self.wgsl(),
+        0,
+        uniforms.len() as u64,
+        Some(STRAIGHT_ALPHA_BLENDING),
+      ));
+    }
+
+    let shader = self.shader.as_ref().unwrap();
+    shader.render(renderer, pass.lock().unwrap(), &uniforms, &[]);
+  }
+}
+
+pub struct ShaderClipBuilder {
+  source: Option<String>,
+  parameters: Vec<(String, Parameter)>,
+  start: f64,
+  end: f64,
+}
+
+impl Default for ShaderClipBuilder {
+  fn default() -> Self {
+    Self {
+      source: None,
+      parameters: vec![],
+      start: 0.0,
+      end: f64::INFINITY,
+    }
+  }
+}
+
+impl ShaderClipBuilder {
+  /// WGSL source containing the `fs_main` fragment entry point
+  pub fn source(mut self, source: impl ToString) -> Self {
+    self.source = Some(source.to_string());
+    self
+  }
+
+  /// Adds a `f32` field called `name` to `uniforms`.
+  ///
+  /// Panics when `name` isn't a valid field name, see [`ShaderClip`]
+  pub fn float(self, name: impl ToString, value: impl Into<Animated<f32>>) -> Self {
+    self.parameter(name.to_string(), Parameter::Float(value.into()))
+  }
+
+  /// Adds a `vec4<f32>` field called `name` to `uniforms`.
+  ///
+  /// Panics when `name` isn't a valid field name, see [`ShaderClip`]
+  pub fn color(self, name: impl ToString, value: impl Into<Animated<Color>>) -> Self {
+    self.parameter(name.to_string(), Parameter::Color(value.into()))
+  }
+
+  fn parameter(mut self, name: String, parameter: Parameter) -> Self {
+    assert!(
+      is_identifier(&name),
+      "shader clip parameter {name:?} is not a WGSL identifier"
+    );
+    assert!(
+      !ShaderClip::BUILTIN_FIELDS.contains(&name.as_str())
+        && self.parameters.iter().all(|(other, _)| *other != name),
+      "shader clip already has a field called {name:?}"
+    );
+
+    self.parameters.push((name, parameter));
+    self
+  }
+
+  pub fn timing(mut self, range: impl Into<std::ops::Range<f64>>) -> Self {
+    let range = range.into();
+    self.start = range.start;
+    self.end = range.end;
+    self
+  }
+
+  pub fn build(self) -> ShaderClip {
+    ShaderClip {
+      source: self.source.expect("shader clip is missing its source"),
+      parameters: self.parameters,
+      start: self.start,
+      end: self.end,
+      shader: None,
+    }
+  }
+}
+
+impl Placed for ShaderClipBuilder {
+  fn placement(&self) -> std::ops::Range<f64> {
+    self.start..self.end
+  }
+
+  fn placement_mut(&mut self) -> (&mut f64, &mut f64) {
+    (&mut self.start, &mut self.end)
+  }
+}
+
+/// Keywords that can't be used as identifiers
+const WGSL_KEYWORDS: [&str; 26] = [
+  "alias",
+  "break",
+  "case",
+  "const",
+  "const_assert",
+  "continue",
+  "continuing",
+  "default",
+  "diagnostic",
+  "discard",
+  "else",
+  "enable",
+  "false",
+  "fn",
+  "for",
+  "if",
+  "let",
+  "loop",
+  "override",
+  "requires",
+  "return",
+  "struct",
+  "switch",
+  "true",
+  "var",
+  "while",
+];
+
+/// Whether `name` can be used as a field name in WGSL: letters, digits and
+/// underscores not starting with a digit or two underscores, and not a keyword
+fn is_identifier(name: &str) -> bool {
+  let mut chars = name.chars();
+  let valid_start = chars
+    .next()
+    .is_some_and(|c| c.is_ascii_alphabetic() || c == '_');
+
+  valid_start
+    && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    && name != "_"
+    && !name.starts_with("__")
+    && !WGSL_KEYWORDS.contains(&name)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn accepts_identifiers() {
+    for name in ["speed", "tint_2", "_offset", "Scale"] {
+      assert!(is_identifier(name), "{name}");
+    }
+  }
+
+  #[test]
+  fn rejects_non_identifiers() {
+    for name in [
+      "",
+      "_",
+      "__private",
+      "my-param",
+      "2nd",
+      "tint color",
+      "fn",
+      "struct",
+    ] {
+      assert!(!is_identifier(name), "{name}");
+    }
+  }
+
+  #[test]
+  #[should_panic(expected = "already has a field called \"time\"")]
+  fn rejects_builtin_fields() {
+    let _ = ShaderClip::builder().float("time", 1.0);
+  }
+
+  #[test]
+  #[should_panic(expected = "already has a field called \"speed\"")]
+  fn rejects_duplicate_parameters() {
+    let _ = ShaderClip::builder()
+      .float("speed", 1.0)
+      .float("speed", 2.0);
+  }
+
+  #[test]
+  #[should_panic(expected = "is not a WGSL identifier")]
+  fn rejects_invalid_names() {
+    let _ = ShaderClip::builder().color("my-param", Color::RED);
+  }
+}
@@ -0,0 +1,179 @@
+use std::sync::Mutex;
+
+use super::animation::Animated;
+use crate::{
+  clip::Clip,
+  render::{progress, Renderer, Time},
+};
+
+/// Wraps a clip to play it with remapped time: slow motion, speed ramps,
+/// freeze frames or backwards. Placement follows the wrapped clip, only the
+/// time inside of it changes, so all of its animated properties stay in sync.
+///
+/// `curve` maps the time since the start of the clip to the time the clip
+/// shows, both in seconds. Keyframing it gives speed ramps:
+///
+/// ```
+/// # use vide::prelude::*;
+/// let remap = TimeRemap::builder()
+///   .clip(Rect::builder().timing(0.0..4.0).build())
+///   .curve(
+///     Animated::builder()
+///       .keyframe(Abs(0.0), ease::LINEAR, 0.0)
+///       // Normal speed for a second, then slow motion for two seconds
+///       .keyframe(Abs(1.0), ease::LINEAR, 1.0)
+///       .keyframe(Abs(3.0), ease::IN_OUT_SINE, 1.5)
+///       .build(),
+///   )
+///   .build();
+/// ```
+pub struct TimeRemap {
+  pub clip: Box<dyn Clip>,
+  /// Time shown by the clip, in seconds since its start
+  pub curve: Animated<f64>,
+}
+
+impl TimeRemap {
+  pub fn builder() -> TimeRemapBuilder {
+    TimeRemapBuilder::default()
+  }
+
+  /// Timing of the clip shown at `time`
+  fn remap(&self, time: Time) -> Time {
+    let clip_frame = (self.curve.evaluate(time).max(0.0) * time.fps).round() as u64;
+    time.derive_clip(
+      clip_frame,
+      clip_frame as f64 / time.fps,
+      progress(clip_frame, time.clip_frames),
+    )
+  }
+}
+
+impl Clip for TimeRemap {
+  fn start(&self, fps: f64) -> u64 {
+    self.clip.start(fps)
+  }
+
+  fn end(&self, video_end: u64, fps: f64) -> u64 {
+    self.clip.end(video_end, fps)
+  }
+
+  fn in_time_frame(&self, frame: u64, fps: f64) -> bool {
+    self.clip.in_time_frame(frame, fps)
+  }
+
+  fn render(&mut self, renderer: &mut Renderer, pass: &Mutex<wgpu::RenderPass<'_>>, time: Time) {
+    let time = self.remap(time);
+    self.clip.render(renderer, pass, time);
+  }
+}
+
+pub struct TimeRemapBuilder {
+  clip: Option<Box<dyn Clip>>,
+  curve: Animated<f64>,
+}
+
+impl Default for TimeRemapBuilder {
+  fn default() -> Self {
+    Self {
+      clip: None,
+      curve: Animated::from_fn(|time: Time| time.clip_time),
+    }
+  }
+}
+
+impl TimeRemapBuilder {
+  pub fn clip(mut self, clip: impl Clip + 'static) -> Self {
+    self.clip = Some(Box::new(clip));
+    self
+  }
+
+  /// Time shown by the clip, in seconds since its start. Defaults to playing
+  /// the clip as is
+  pub fn curve(mut self, curve: impl Into<Animated<f64>>) -> Self {
+    self.curve = curve.into();
+    self
+  }
+
+  /// Plays the clip `speed` times as fast
+  pub fn speed(self, speed: f64) -> Self {
+    self.curve(Animated::from_fn(move |time: Time| time.clip_time * speed))
+  }
+
+  /// Shows the clip as it is `at` seconds after its start the whole time
+  pub fn freeze(self, at: f64) -> Self {
+    self.curve(at)
+  }
+
+  /// Plays the first `duration` seconds of the clip backwards
+  pub fn reverse(self, duration: f64) -> Self {
+    self.curve(Animated::from_fn(move |time: Time| {
+      duration - time.clip_time
+    }))
+  }
+
+  pub fn build(self) -> TimeRemap {
+    TimeRemap {
+      clip: self.clip.expect("time remap is missing its clip"),
+      curve: self.curve,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::api::rect::Rect;
+
+  /// Frame and progress of the clip shown at `frame` of a 60 fps clip that is
+  /// 4 seconds long
+  fn remapped(builder: TimeRemapBuilder, frame: u64) -> (u64, f64) {
+    let remap = builder
+      .clip(Rect::builder().timing(0.0..4.0).build())
+      .build();
+    let time = remap.remap(Time::new(frame, 240, 60.0));
+    (time.clip_frame, time.clip_progress)
+  }
+
+  #[test]
+  fn speed_scales_time() {
+    let fast = TimeRemap::builder().speed(2.0);
+    assert_eq!(remapped(fast, 60), (120, 0.5));
+
+    let slow = TimeRemap::builder().speed(0.5);
+    assert_eq!(remapped(slow, 60), (30, 0.125));
+  }
+
+  #[test]
+  fn freeze_shows_one_frame() {
+    for frame in [0, 100, 239] {
+      assert_eq!(
+        remapped(TimeRemap::builder().freeze(1.0), frame),
+        (60, 0.25)
+      );
+    }
+  }
+
+  #[test]
+  fn reverse_plays_backwards() {
+    let reverse = || TimeRemap::builder().reverse(2.0);
+    assert_eq!(remapped(reverse(), 0), (120, 0.5));
+    assert_eq!(remapped(reverse(), 60), (60, 0.25));
+    assert_eq!(remapped(reverse(), 120), (0, 0.0));
+    // Holds the first frame once the reversed part is over
+    assert_eq!(remapped(reverse(), 180), (0, 0.0));
+  }
+
+  #[test]
+  fn the_clip_becomes_the_parent() {
+    let remap = TimeRemap::builder()
+      .speed(2.0)
+      .clip(Rect::builder().build())
+      .build();
+    let time = remap.remap(Time::new(30, 240, 60.0).child(10, 130));
+
+    assert_eq!(time.sequence_frame, 20);
+    assert_eq!(time.clip_frame, 40);
+    assert_eq!(time.clip_frames, 120);
+  }
+}
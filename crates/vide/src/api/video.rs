@@ -1,6 +1,16 @@
-use std::{collections::VecDeque, time::Duration};
-
-use crate::{api::color::Color, app::App, clip::Clip, io::Export, rgb8};
+use std::{collections::VecDeque, path::PathBuf, time::Duration};
+
+use crate::{
+  api::{
+    color::Color,
+    effect::{Effect, EffectStack},
+  },
+  app::{reload::Reload, App},
+  clip::{Clip, IntoFrame},
+  io::Export,
+  render::{self, ExportOutput, Output, Renderer, Time},
+  rgb8,
+};
 
 #[derive(Debug, Clone, Copy)]
 pub struct VideoSettings {
@@ -22,38 +32,34 @@ impl Default for VideoSettings {
 }
 
 pub struct Video {
-  #[cfg(not(feature = "preview"))]
-  pub renderer: Renderer,
   clips: VecDeque<Box<dyn Clip>>,
+  effects: EffectStack,
+  reload: Option<Reload>,
   pub settings: VideoSettings,
 }
 
 impl Video {
   pub fn new(settings: VideoSettings) -> Self {
-    #[cfg(feature = "preview")]
-    {
-      Self {
-        clips: VecDeque::new(),
-        settings,
-      }
-    }
-
-    #[cfg(not(feature = "preview"))]
     Self {
-      renderer: Renderer::new(settings),
       clips: VecDeque::new(),
+      effects: EffectStack::opaque(),
+      reload: None,
       settings,
     }
   }
 
-  pub fn render(mut self, exporter: impl Export)
+  /// Previews the video when the program was started with `--preview` and
+  /// exports it with `exporter` otherwise, see [`Video::preview`] and
+  /// [`Video::export`]
+  pub fn render(self, exporter: impl Export)
   where
     Self: 'static,
   {
-    #[cfg(feature = "preview")]
-    self.preview();
-    #[cfg(not(feature = "preview"))]
-    self.export(exporter);
+    if std::env::args().skip(1).any(|arg| arg == "--preview") {
+      self.preview();
+    } else {
+      self.export(exporter);
+    }
   }
 
   #[inline]
@@ -77,38 +83,74 @@ impl Video {
     self.clips.remove(index);
   }
 
-  #[cfg(feature = "preview")]
-  fn preview(self)
+  /// Adds an effect applied to every frame of the video, after all previously
+  /// pushed effects
+  #[inline]
+  pub fn push_effect(&mut self, effect: impl Effect + 'static) {
+    self.effects.push(effect);
+  }
+
+  /// Builds all clips and effects of the video with `scene`, replacing any
+  /// added before. While previewing, the scene is rebuilt whenever one of
+  /// `paths` changes, staying on the current frame. Point it to the files the
+  /// scene reads its keyframes or timing from to see changes without
+  /// restarting:
+  ///
+  /// ```no_run
+  /// # use vide::prelude::*;
+  /// # let mut video = Video::new(VideoSettings::default());
+  /// video.watch(["scene.txt"], |video| {
+  ///   let delay: f64 = std::fs::read_to_string("scene.txt")
+  ///     .unwrap()
+  ///     .trim()
+  ///     .parse()
+  ///     .unwrap();
+  ///   video.push_clip(Rect::builder().timing(delay..4.0).build());
+  /// });
+  /// ```
+  ///
+  /// If the scene panics while being rebuilt, the previous one stays.
+  pub fn watch<P: Into<PathBuf>>(
+    &mut self,
+    paths: impl IntoIterator<Item = P>,
+    mut scene: impl FnMut(&mut Video) + 'static,
+  ) {
+    self.clips.clear();
+    self.effects = EffectStack::opaque();
+    scene(self);
+
+    let paths = paths.into_iter().map(Into::into).collect();
+    self.reload = Some(Reload::new(paths, Box::new(scene)));
+  }
+
+  /// Takes the clips and effects, e.g. of a rebuilt scene
+  pub(crate) fn into_scene(self) -> (VecDeque<Box<dyn Clip>>, EffectStack) {
+    (self.clips, self.effects)
+  }
+
+  /// Plays the video in a window, blocking until it's closed
+  pub fn preview(self)
   where
     Self: 'static,
   {
-    let (event_loop, mut app) = App::new(self.settings, self.clips);
+    let (event_loop, mut app) = App::new(self.settings, self.clips, self.effects, self.reload);
     event_loop.run_app(&mut app).unwrap();
   }
 
-  #[cfg(not(feature = "preview"))]
-  fn export(mut self, mut exporter: impl Export) {
-    use crate::{clip::IntoFrame, render::RenderEvent};
-
-    exporter.begin(self.settings);
+  /// Renders every frame of the video and encodes them with `exporter`
+  pub fn export(mut self, exporter: impl Export) {
+    let mut renderer = Renderer::new(self.settings);
+    let mut output = ExportOutput::new(&renderer, exporter);
 
-    let total_frames = self.settings.duration.into_frame(self.settings.fps);
+    let fps = self.settings.fps;
+    let total_frames = self.settings.duration.into_frame(fps);
     for frame in 0..total_frames {
-      let mut events = vec![];
-      for clip in self.clips.iter_mut() {
-        let start_frame = clip.start();
-        if clip.in_time_frame(frame) {
-          events.push(RenderEvent::Clip {
-            clip: clip.as_mut(),
-            frame: frame - start_frame,
-          });
-        }
-      }
-
-      let frame_data = self.renderer.render(events).unwrap();
-      exporter.push_frame(true, &frame_data);
+      let time = Time::new(frame, total_frames, fps);
+      let events = render::clip_events(&mut self.clips, time, total_frames);
+      renderer.render(events, &mut self.effects, time);
+      output.present(&mut renderer, vec![]);
     }
 
-    exporter.end();
+    output.end();
   }
 }
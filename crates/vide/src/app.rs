@@ -1,26 +1,66 @@
-use std::{collections::VecDeque, sync::Arc};
+use std::{
+  collections::VecDeque,
+  sync::Arc,
+  time::{Duration, Instant},
+};
+
+use winit::{
+  event::{ElementState, KeyEvent, MouseButton, MouseScrollDelta, StartCause, WindowEvent},
+  event_loop::{ActiveEventLoop, ControlFlow},
+  keyboard::{Key, ModifiersState, NamedKey},
+};
 
+use self::{
+  frame_rate::FrameRate, playback::Playback, reload::Reload, scrubber::Scrubber, viewport::Viewport,
+};
 use crate::{
-  clip::Clip,
+  api::effect::EffectStack,
+  clip::{Clip, IntoFrame},
   prelude::VideoSettings,
-  render::{RenderEvent, Renderer},
+  render::{self, Output, RenderEvent, Renderer, Time, WindowOutput},
 };
 
+mod frame_rate;
+mod playback;
+pub(crate) mod reload;
+mod scrubber;
+mod viewport;
+
+const TITLE: &str = "Vide";
+/// How often the measured fps in the window title is refreshed
+const TITLE_INTERVAL: Duration = Duration::from_millis(500);
+
 pub struct App {
   window: Option<Arc<winit::window::Window>>,
   settings: VideoSettings,
   renderer: Option<Renderer>,
-  frame: u64,
+  output: Option<WindowOutput>,
+  playback: Playback,
+  scrubber: Scrubber,
+  frame_rate: FrameRate,
+  viewport: Viewport,
+  /// Whether the video is being dragged around with the middle or right mouse
+  /// button
+  panning: bool,
+  /// When the window title was last refreshed
+  title_updated: Option<Instant>,
+  /// Last known mouse position, in physical window pixels
+  cursor: (f64, f64),
+  modifiers: ModifiersState,
   clips: VecDeque<Box<dyn Clip>>,
+  effects: EffectStack,
+  reload: Option<Reload>,
 }
 
 impl App {
   pub fn new(
     settings: VideoSettings,
     clips: VecDeque<Box<dyn Clip>>,
+    effects: EffectStack,
+    reload: Option<Reload>,
   ) -> (winit::event_loop::EventLoop<()>, Self) {
     let event_loop = winit::event_loop::EventLoop::new().unwrap();
-    event_loop.set_control_flow(winit::event_loop::ControlFlow::Poll);
+    event_loop.set_control_flow(ControlFlow::Wait);
 
     (
       event_loop,
@@ -28,59 +68,269 @@ impl App {
         window: None,
         settings,
         renderer: None,
-        frame: 0,
+        output: None,
+        playback: Playback::new(settings.duration.into_frame(settings.fps), settings.fps),
+        scrubber: Scrubber::default(),
+        frame_rate: FrameRate::default(),
+        viewport: Viewport::new(settings.resolution, settings.resolution),
+        panning: false,
+        title_updated: None,
+        cursor: (0.0, 0.0),
+        modifiers: ModifiersState::empty(),
         clips,
+        effects,
+        reload,
       },
     )
   }
+
+  /// Scale factor of the window, overlays are sized in logical pixels
+  fn ui_scale(&self) -> f32 {
+    self
+      .window
+      .as_ref()
+      .map_or(1.0, |window| window.scale_factor() as f32)
+  }
+
+  fn scrub(&mut self) {
+    let frame = self.scrubber.frame_at(
+      self.cursor.0,
+      &self.playback,
+      self.viewport.window,
+      self.ui_scale(),
+    );
+    self.playback.seek(frame);
+  }
+
+  fn request_redraw(&self) {
+    if let Some(window) = &self.window {
+      window.request_redraw();
+    }
+  }
+
+  /// Wakes up when the next frame is due or the watched files should be
+  /// checked, or sleeps until there's input when neither is needed
+  fn schedule(&self, event_loop: &ActiveEventLoop) {
+    let next_frame = self
+      .playback
+      .next_frame_at()
+      .filter(|_| !self.scrubber.dragging);
+    let next_check = self.reload.as_ref().map(Reload::next_check);
+    match next_frame.into_iter().chain(next_check).min() {
+      Some(at) => event_loop.set_control_flow(ControlFlow::WaitUntil(at)),
+      None => event_loop.set_control_flow(ControlFlow::Wait),
+    }
+  }
+
+  /// Swaps in the rebuilt scene when a watched file changed, the current
+  /// frame stays
+  fn reload(&mut self, now: Instant) {
+    let Some(reload) = &mut self.reload else {
+      return;
+    };
+
+    if let Some((clips, effects)) = reload.poll(now, self.settings) {
+      self.clips = clips;
+      self.effects = effects;
+      self.request_redraw();
+    }
+  }
+
+  /// Shows the measured fps in the window title while playing
+  fn update_title(&mut self, now: Instant) {
+    let Some(window) = &self.window else {
+      return;
+    };
+
+    if !self.playback.playing {
+      window.set_title(TITLE);
+      self.title_updated = None;
+    } else if self
+      .title_updated
+      .is_none_or(|updated| now.duration_since(updated) >= TITLE_INTERVAL)
+    {
+      window.set_title(&format!(
+        "{TITLE} - {}",
+        self.frame_rate.summary(self.settings.fps)
+      ));
+      self.title_updated = Some(now);
+    }
+  }
+
+  fn render(&mut self) {
+    let now = Instant::now();
+    // Scrubbing holds the frame under the mouse
+    if self.scrubber.dragging {
+      self.playback.restart_clock();
+    }
+    let previous = self.playback.frame;
+    let dropped = self.playback.tick(now);
+    if !self.playback.playing || self.scrubber.dragging {
+      self.frame_rate.reset();
+    } else if self.playback.frame != previous {
+      // Redraws for input while holding a frame don't count as shown frames
+      self.frame_rate.record(now, dropped);
+    }
+    self.update_title(now);
+
+    let ui_scale = self.ui_scale();
+    let (Some(renderer), Some(output)) = (&mut self.renderer, &mut self.output) else {
+      return;
+    };
+
+    let fps = self.settings.fps;
+    let total_frames = self.playback.frames;
+    let time = Time::new(self.playback.frame, total_frames, fps);
+    let events = render::clip_events(&mut self.clips, time, total_frames);
+    renderer.render(events, &mut self.effects, time);
+
+    let window = self.viewport.window;
+    let mut overlay = self.scrubber.rects(&self.playback, window, ui_scale);
+    if self.scrubber.visible {
+      overlay.extend(self.frame_rate.rects(fps, window, ui_scale));
+    }
+    let overlay = overlay
+      .iter_mut()
+      .map(|rect| RenderEvent::Clip {
+        clip: rect,
+        time: Time::default(),
+      })
+      .collect();
+
+    output.set_viewport(
+      renderer,
+      self.viewport.scale(),
+      self.viewport.offset(),
+      Viewport::LETTERBOX,
+    );
+    output.present(renderer, overlay);
+  }
 }
 
 impl winit::application::ApplicationHandler for App {
-  fn resumed(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
+  fn new_events(&mut self, event_loop: &ActiveEventLoop, cause: StartCause) {
+    if let StartCause::ResumeTimeReached { .. } = cause {
+      let now = Instant::now();
+      self.reload(now);
+      if self.playback.next_frame_at().is_some_and(|at| at <= now) {
+        self.request_redraw();
+      }
+      self.schedule(event_loop);
+    }
+  }
+
+  fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+    let monitor = event_loop
+      .primary_monitor()
+      .map(|monitor| (monitor.size().width, monitor.size().height));
+    let (width, height) = Viewport::initial_window_size(self.settings.resolution, monitor);
     let window = Arc::new(
       event_loop
         .create_window(
           winit::window::WindowAttributes::default()
-            .with_inner_size(winit::dpi::PhysicalSize::new(
-              self.settings.resolution.0,
-              self.settings.resolution.1,
-            ))
-            .with_resizable(false),
+            .with_title(TITLE)
+            .with_inner_size(winit::dpi::PhysicalSize::new(width, height)),
         )
         .unwrap(),
     );
 
-    self.renderer = Some(Renderer::new(self.settings, window.clone()));
+    let size = window.inner_size();
+    self.viewport.resize((size.width, size.height));
+    let renderer = Renderer::new(self.settings);
+    self.output = Some(WindowOutput::new(&renderer, window.clone()));
+    self.renderer = Some(renderer);
     self.window = Some(window);
+    self.request_redraw();
   }
 
   fn window_event(
     &mut self,
-    event_loop: &winit::event_loop::ActiveEventLoop,
+    event_loop: &ActiveEventLoop,
     _id: winit::window::WindowId,
-    event: winit::event::WindowEvent,
+    event: WindowEvent,
   ) {
     match event {
-      winit::event::WindowEvent::CloseRequested => {
+      WindowEvent::CloseRequested => {
         event_loop.exit();
       }
-      winit::event::WindowEvent::RedrawRequested => {
-        if let Some(renderer) = &mut self.renderer {
-          let mut events = vec![];
-          for clip in self.clips.iter_mut() {
-            let start_frame = clip.start();
-            if clip.in_time_frame(self.frame) {
-              events.push(RenderEvent::Clip {
-                clip: clip.as_mut(),
-                frame: self.frame - start_frame,
-              });
-            }
-          }
-          renderer.render(events);
-          self.frame =
-            (self.frame + 1) % (self.settings.duration.as_secs_f64() * self.settings.fps) as u64;
+      WindowEvent::Resized(size) => {
+        self.viewport.resize((size.width, size.height));
+        if let (Some(renderer), Some(output)) = (&self.renderer, &mut self.output) {
+          output.resize(renderer, (size.width, size.height));
+        }
+        self.request_redraw();
+      }
+      WindowEvent::ModifiersChanged(modifiers) => {
+        self.modifiers = modifiers.state();
+      }
+      WindowEvent::KeyboardInput {
+        event:
+          KeyEvent {
+            logical_key,
+            state: ElementState::Pressed,
+            ..
+          },
+        ..
+      } => {
+        let handled = self
+          .playback
+          .handle_key(&logical_key, self.modifiers.shift_key())
+          || self.viewport.handle_key(&logical_key);
+        if !handled && logical_key == Key::Named(NamedKey::Tab) {
+          self.scrubber.visible = !self.scrubber.visible;
+        }
+        self.request_redraw();
+      }
+      WindowEvent::CursorMoved { position, .. } => {
+        let delta = (position.x - self.cursor.0, position.y - self.cursor.1);
+        self.cursor = (position.x, position.y);
+        if self.scrubber.dragging {
+          self.scrub();
+          self.request_redraw();
+        } else if self.panning {
+          self.viewport.pan_by(delta);
+          self.request_redraw();
         }
-        self.window.as_ref().unwrap().request_redraw();
+      }
+      WindowEvent::MouseWheel { delta, .. } => {
+        let notches = match delta {
+          MouseScrollDelta::LineDelta(_, y) => y as f64,
+          // Roughly one notch per 60 pixels of touchpad scrolling
+          MouseScrollDelta::PixelDelta(position) => position.y / 60.0,
+        };
+        self.viewport.scroll(notches, self.cursor);
+        self.request_redraw();
+      }
+      WindowEvent::MouseInput {
+        state,
+        button: MouseButton::Middle | MouseButton::Right,
+        ..
+      } => {
+        self.panning = state == ElementState::Pressed;
+      }
+      WindowEvent::MouseInput {
+        state,
+        button: MouseButton::Left,
+        ..
+      } => match state {
+        ElementState::Pressed
+          if self
+            .scrubber
+            .contains(self.cursor, self.viewport.window, self.ui_scale()) =>
+        {
+          self.scrubber.dragging = true;
+          self.scrub();
+          self.request_redraw();
+        }
+        ElementState::Released if self.scrubber.dragging => {
+          self.scrubber.dragging = false;
+          self.request_redraw();
+        }
+        _ => (),
+      },
+      WindowEvent::RedrawRequested => {
+        self.render();
+        self.schedule(event_loop);
       }
       _ => (),
     }
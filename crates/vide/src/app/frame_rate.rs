@@ -0,0 +1,183 @@
+use std::{
+  collections::VecDeque,
+  time::{Duration, Instant},
+};
+
+use crate::{api::rect::Rect, rgba8};
+
+/// Measures how many frames the preview actually shows per second and draws
+/// it against the video's fps as a meter in the top right corner of the
+/// window, full when playback keeps up.
+///
+/// Sizes are in logical pixels and scale with the window's `scale` factor.
+#[derive(Debug, Default)]
+pub struct FrameRate {
+  /// When the frames of the last second were shown
+  frames: VecDeque<Instant>,
+  /// Frames skipped during the last second
+  dropped: VecDeque<(Instant, u64)>,
+}
+
+impl FrameRate {
+  const MARGIN: f32 = 24.0;
+  const SIZE: (f32, f32) = (160.0, 6.0);
+  const WINDOW: Duration = Duration::from_secs(1);
+
+  /// Records a frame shown at `now` after skipping `dropped` frames
+  pub fn record(&mut self, now: Instant, dropped: u64) {
+    self.frames.push_back(now);
+    if dropped > 0 {
+      self.dropped.push_back((now, dropped));
+    }
+
+    let since = now.checked_sub(Self::WINDOW).unwrap_or(now);
+    while self.frames.front().is_some_and(|frame| *frame < since) {
+      self.frames.pop_front();
+    }
+    while self
+      .dropped
+      .front()
+      .is_some_and(|(frame, _)| *frame < since)
+    {
+      self.dropped.pop_front();
+    }
+  }
+
+  /// Forgets all frames, e.g. while paused where there is nothing to measure
+  pub fn reset(&mut self) {
+    self.frames.clear();
+    self.dropped.clear();
+  }
+
+  /// Frames shown per second, `None` until there are enough frames to tell
+  pub fn fps(&self) -> Option<f64> {
+    let (first, last) = (self.frames.front()?, self.frames.back()?);
+    let elapsed = last.duration_since(*first).as_secs_f64();
+    (self.frames.len() > 2 && elapsed > 0.0).then(|| (self.frames.len() - 1) as f64 / elapsed)
+  }
+
+  /// Frames skipped during the last second
+  pub fn dropped(&self) -> u64 {
+    self.dropped.iter().map(|(_, dropped)| dropped).sum()
+  }
+
+  /// Short summary for the window title, like `58.7 / 60 fps (3 dropped)`
+  pub fn summary(&self, target: f64) -> String {
+    let actual = self
+      .fps()
+      .map_or_else(|| "-".to_string(), |fps| format!("{fps:.1}"));
+    match self.dropped() {
+      0 => format!("{actual} / {target} fps"),
+      dropped => format!("{actual} / {target} fps ({dropped} dropped)"),
+    }
+  }
+
+  /// Rects drawing the meter, centered like all clips. Empty while there's
+  /// nothing measured
+  pub fn rects(&self, target: f64, window: (u32, u32), scale: f32) -> Vec<Rect> {
+    let Some(fps) = self.fps() else {
+      return vec![];
+    };
+
+    let ratio = (fps / target).clamp(0.0, 1.0) as f32;
+    let color = if ratio >= 0.95 {
+      rgba8!(0x40, 0xd0, 0x60, 0xff)
+    } else if ratio >= 0.75 {
+      rgba8!(0xff, 0xc8, 0x00, 0xff)
+    } else {
+      rgba8!(0xff, 0x40, 0x40, 0xff)
+    };
+
+    let (width, height) = (Self::SIZE.0 * scale, Self::SIZE.1 * scale);
+    let right = window.0 as f32 / 2.0 - Self::MARGIN * scale;
+    let y = window.1 as f32 / 2.0 - Self::MARGIN * scale - height / 2.0;
+    let bar = |from: f32, to: f32, color| {
+      Rect::builder()
+        .position(((from + to) / 2.0, y))
+        .size(((to - from).max(1.0), height))
+        .color(color)
+        .rounded(0.5) // 50%
+        .build()
+    };
+
+    vec![
+      bar(right - width, right, rgba8!(0x00, 0x00, 0x00, 0x80)),
+      bar(right - width, right - width * (1.0 - ratio), color),
+    ]
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Records `count` frames every `interval` starting at `start`, returns
+  /// when the last one was shown
+  fn record(frame_rate: &mut FrameRate, start: Instant, count: u32, interval: Duration) -> Instant {
+    for i in 0..count {
+      frame_rate.record(start + interval * i, 0);
+    }
+    start + interval * count.saturating_sub(1)
+  }
+
+  #[test]
+  fn steady_frames_measure_their_rate() {
+    let mut frame_rate = FrameRate::default();
+    record(
+      &mut frame_rate,
+      Instant::now(),
+      30,
+      Duration::from_millis(20),
+    );
+
+    let fps = frame_rate.fps().unwrap();
+    assert!((fps - 50.0).abs() < 1e-6, "{fps}");
+    assert_eq!(frame_rate.dropped(), 0);
+  }
+
+  #[test]
+  fn a_few_frames_are_not_enough() {
+    let mut frame_rate = FrameRate::default();
+    assert_eq!(frame_rate.fps(), None);
+
+    let start = Instant::now();
+    record(&mut frame_rate, start, 2, Duration::from_millis(20));
+    assert_eq!(frame_rate.fps(), None);
+
+    frame_rate.record(start + Duration::from_millis(40), 0);
+    assert!(frame_rate.fps().is_some());
+
+    frame_rate.reset();
+    assert_eq!(frame_rate.fps(), None);
+  }
+
+  #[test]
+  fn long_gaps_count_dropped_frames() {
+    let mut frame_rate = FrameRate::default();
+    let interval = Duration::from_millis(20);
+    let last = record(&mut frame_rate, Instant::now(), 10, interval);
+
+    frame_rate.record(last + interval * 4, 3);
+    frame_rate.record(last + interval * 5, 0);
+    frame_rate.record(last + interval * 8, 2);
+    assert_eq!(frame_rate.dropped(), 5);
+
+    // Only the last second counts
+    frame_rate.record(last + interval * 8 + Duration::from_millis(1500), 0);
+    assert_eq!(frame_rate.dropped(), 0);
+  }
+
+  #[test]
+  fn summaries_show_the_rate_and_drops() {
+    let mut frame_rate = FrameRate::default();
+    assert_eq!(frame_rate.summary(60.0), "- / 60 fps");
+
+    let interval = Duration::from_millis(20);
+    let last = record(&mut frame_rate, Instant::now(), 11, interval);
+    assert_eq!(frame_rate.summary(60.0), "50.0 / 60 fps");
+    assert_eq!(frame_rate.summary(29.97), "50.0 / 29.97 fps");
+
+    frame_rate.record(last + interval * 2, 1);
+    assert_eq!(frame_rate.summary(60.0), "45.8 / 60 fps (1 dropped)");
+  }
+}
@@ -0,0 +1,284 @@
+use std::{
+  ops::Range,
+  time::{Duration, Instant},
+};
+
+use winit::keyboard::{Key, NamedKey};
+
+/// Fastest J/L shuttle speed, in multiples of real time
+const MAX_SHUTTLE: i64 = 8;
+
+/// Transport state of the preview: which frame is shown, whether and how fast
+/// it plays and which part of the video loops.
+///
+/// Playback follows the wall clock at the video's fps. Frames are skipped
+/// when rendering can't keep up and held when it's faster.
+#[derive(Debug, Clone)]
+pub struct Playback {
+  pub frame: u64,
+  /// Number of frames in the video
+  pub frames: u64,
+  pub fps: f64,
+  pub playing: bool,
+  /// Playback speed in multiples of real time, negative plays backwards
+  pub shuttle: i64,
+  /// In and out points, playback wraps around inside of them
+  pub loop_range: Range<u64>,
+  /// When playback last caught up with the clock
+  last_tick: Option<Instant>,
+  /// Part of a frame that has played since the current one started
+  remainder: f64,
+}
+
+impl Playback {
+  pub fn new(frames: u64, fps: f64) -> Self {
+    let frames = frames.max(1);
+    Self {
+      frame: 0,
+      frames,
+      fps,
+      playing: true,
+      shuttle: 1,
+      loop_range: 0..frames,
+      last_tick: None,
+      remainder: 0.0,
+    }
+  }
+
+  /// Catches up with the clock, returns how many frames were skipped because
+  /// the previous frame took too long
+  pub fn tick(&mut self, now: Instant) -> u64 {
+    let elapsed = self
+      .last_tick
+      .map_or(0.0, |last| now.duration_since(last).as_secs_f64());
+    self.last_tick = Some(now);
+    if !self.playing {
+      return 0;
+    }
+
+    let frames = elapsed * self.fps * self.shuttle.unsigned_abs() as f64 + self.remainder;
+    let whole = frames.floor();
+    self.remainder = frames - whole;
+    self.advance_by(whole as i64 * self.shuttle.signum());
+
+    // Shuttling faster than real time skips frames on purpose
+    (whole as u64).saturating_sub(self.shuttle.unsigned_abs())
+  }
+
+  /// When the next frame is due, `None` when paused
+  pub fn next_frame_at(&self) -> Option<Instant> {
+    let last_tick = self.last_tick.filter(|_| self.playing)?;
+    let rate = self.fps * self.shuttle.unsigned_abs() as f64;
+    Some(last_tick + Duration::from_secs_f64((1.0 - self.remainder) / rate))
+  }
+
+  /// Starts timing from scratch, e.g. after being paused or held, so the time
+  /// in between isn't played
+  pub fn restart_clock(&mut self) {
+    self.last_tick = None;
+    self.remainder = 0.0;
+  }
+
+  /// Moves `frames` frames, wrapping around inside of the loop range
+  pub fn advance_by(&mut self, frames: i64) {
+    if frames == 0 {
+      return;
+    }
+
+    let Range { start, end } = self.loop_range;
+    let length = (end - start) as i64;
+    if !self.loop_range.contains(&self.frame) {
+      // Playing from outside of the loop range starts it over
+      self.frame = if frames < 0 { end - 1 } else { start };
+      return;
+    }
+
+    let offset = (self.frame - start) as i64 + frames;
+    self.frame = start + offset.rem_euclid(length) as u64;
+  }
+
+  /// Shows `frame` (clamped to the video)
+  pub fn seek(&mut self, frame: u64) {
+    self.frame = frame.min(self.frames - 1);
+  }
+
+  /// Pauses and moves `frames` frames, without wrapping around
+  pub fn step(&mut self, frames: i64) {
+    self.playing = false;
+    self.seek(self.frame.saturating_add_signed(frames));
+  }
+
+  pub fn toggle(&mut self) {
+    self.playing = !self.playing;
+    self.restart_clock();
+  }
+
+  /// J/L shuttle: plays in `direction`, pressing again speeds up
+  pub fn shuttle(&mut self, direction: i64) {
+    if self.playing && self.shuttle.signum() == direction {
+      self.shuttle = (self.shuttle * 2).clamp(-MAX_SHUTTLE, MAX_SHUTTLE);
+    } else {
+      self.shuttle = direction;
+      self.playing = true;
+      self.restart_clock();
+    }
+  }
+
+  pub fn pause(&mut self) {
+    self.playing = false;
+    self.shuttle = 1;
+  }
+
+  /// Sets the loop's in point to the current frame
+  pub fn set_in(&mut self) {
+    self.loop_range.start = self.frame;
+    if self.loop_range.end <= self.frame {
+      self.loop_range.end = self.frames;
+    }
+  }
+
+  /// Sets the loop's out point to the current frame, which is still played
+  pub fn set_out(&mut self) {
+    self.loop_range.end = self.frame + 1;
+    if self.loop_range.start > self.frame {
+      self.loop_range.start = 0;
+    }
+  }
+
+  pub fn clear_loop(&mut self) {
+    self.loop_range = 0..self.frames;
+  }
+
+  /// Handles a pressed key, returns whether it was a playback control:
+  ///
+  /// - `Space`: play/pause
+  /// - `←`/`→`: step one frame (`Shift` for one second)
+  /// - `J`/`K`/`L`: shuttle backwards, pause, shuttle forwards
+  /// - `Home`/`End`: jump to the loop's in/out point
+  /// - `0`-`9`: jump to 0% - 90% of the video
+  /// - `I`/`O`: set the loop's in/out point, `X` clears them
+  pub fn handle_key(&mut self, key: &Key, shift: bool) -> bool {
+    let step = if shift {
+      self.fps.round().max(1.0) as i64
+    } else {
+      1
+    };
+    match key {
+      Key::Named(NamedKey::Space) => self.toggle(),
+      Key::Named(NamedKey::ArrowLeft) => self.step(-step),
+      Key::Named(NamedKey::ArrowRight) => self.step(step),
+      Key::Named(NamedKey::Home) => self.seek(self.loop_range.start),
+      Key::Named(NamedKey::End) => self.seek(self.loop_range.end - 1),
+      Key::Character(character) => match character.to_lowercase().as_str() {
+        "j" => self.shuttle(-1),
+        "k" => self.pause(),
+        "l" => self.shuttle(1),
+        "i" => self.set_in(),
+        "o" => self.set_out(),
+        "x" => self.clear_loop(),
+        digit => match digit.parse::<u64>() {
+          Ok(digit) if digit < 10 => self.seek(self.frames * digit / 10),
+          _ => return false,
+        },
+      },
+      _ => return false,
+    }
+
+    true
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn looping(frame: u64, loop_range: Range<u64>) -> Playback {
+    let mut playback = Playback::new(100, 10.0);
+    playback.frame = frame;
+    playback.loop_range = loop_range;
+    playback
+  }
+
+  #[test]
+  fn loops_wrap_in_both_directions() {
+    let mut playback = looping(28, 20..30);
+    playback.advance_by(3);
+    assert_eq!(playback.frame, 21);
+
+    playback.advance_by(-3);
+    assert_eq!(playback.frame, 28);
+
+    playback.advance_by(25);
+    assert_eq!(playback.frame, 23);
+  }
+
+  #[test]
+  fn playing_from_outside_the_loop_starts_it_over() {
+    let mut playback = looping(50, 20..30);
+    playback.advance_by(1);
+    assert_eq!(playback.frame, 20);
+
+    let mut playback = looping(5, 20..30);
+    playback.advance_by(-1);
+    assert_eq!(playback.frame, 29);
+  }
+
+  #[test]
+  fn ticks_follow_the_clock() {
+    let mut playback = Playback::new(100, 10.0);
+    let start = Instant::now();
+    assert_eq!(playback.tick(start), 0);
+
+    assert_eq!(playback.tick(start + Duration::from_millis(250)), 1);
+    assert_eq!(playback.frame, 2);
+    // The half frame left over counts towards the next one
+    playback.tick(start + Duration::from_millis(300));
+    assert_eq!(playback.frame, 3);
+  }
+
+  #[test]
+  fn shuttling_again_doubles_the_speed_up_to_the_limit() {
+    let mut playback = Playback::new(100, 10.0);
+    for expected in [2, 4, 8, 8] {
+      playback.shuttle(1);
+      assert_eq!(playback.shuttle, expected);
+    }
+
+    playback.shuttle(-1);
+    assert_eq!(playback.shuttle, -1);
+    for expected in [-2, -4, -8, -8] {
+      playback.shuttle(-1);
+      assert_eq!(playback.shuttle, expected);
+    }
+
+    playback.pause();
+    playback.shuttle(-1);
+    assert_eq!(playback.shuttle, -1);
+    assert!(playback.playing);
+  }
+
+  #[test]
+  fn digits_seek_to_tenths_of_the_video() {
+    let mut playback = Playback::new(250, 10.0);
+    for (digit, frame) in [("0", 0), ("5", 125), ("9", 225)] {
+      assert!(playback.handle_key(&Key::Character(digit.into()), false));
+      assert_eq!(playback.frame, frame);
+    }
+
+    assert!(!playback.handle_key(&Key::Character("q".into()), false));
+    assert_eq!(playback.frame, 225);
+  }
+
+  #[test]
+  fn steps_and_seeks_stay_in_the_video() {
+    let mut playback = Playback::new(100, 10.0);
+    playback.step(-1);
+    assert_eq!(playback.frame, 0);
+    assert!(!playback.playing);
+
+    playback.seek(500);
+    assert_eq!(playback.frame, 99);
+    playback.handle_key(&Key::Named(NamedKey::ArrowLeft), true);
+    assert_eq!(playback.frame, 89);
+  }
+}
@@ -0,0 +1,197 @@
+use std::{
+  collections::VecDeque,
+  panic::{self, AssertUnwindSafe},
+  path::{Path, PathBuf},
+  time::{Duration, Instant, SystemTime},
+};
+
+use crate::{
+  api::{effect::EffectStack, video::Video},
+  clip::Clip,
+  prelude::VideoSettings,
+};
+
+/// Scene built by [`Video::watch`], rebuilt whenever one of the files it
+/// watches changes
+pub struct Reload {
+  scene: Box<dyn FnMut(&mut Video)>,
+  paths: Vec<PathBuf>,
+  /// Last modification time of each path, `None` while it can't be read
+  modified: Vec<Option<SystemTime>>,
+  checked: Instant,
+}
+
+impl Reload {
+  /// How often the watched files are checked
+  const INTERVAL: Duration = Duration::from_millis(250);
+
+  pub fn new(paths: Vec<PathBuf>, scene: Box<dyn FnMut(&mut Video)>) -> Self {
+    Self {
+      modified: paths.iter().map(|path| modified(path)).collect(),
+      scene,
+      paths,
+      checked: Instant::now(),
+    }
+  }
+
+  /// When the files should be checked again
+  pub fn next_check(&self) -> Instant {
+    self.checked + Self::INTERVAL
+  }
+
+  /// Rebuilds the scene if a watched file changed since the last check,
+  /// returning its clips and effects. A scene that panics while building
+  /// keeps the previous one on screen
+  pub fn poll(
+    &mut self,
+    now: Instant,
+    settings: VideoSettings,
+  ) -> Option<(VecDeque<Box<dyn Clip>>, EffectStack)> {
+    if now < self.next_check() {
+      return None;
+    }
+    self.checked = now;
+
+    let mut changed = false;
+    for (path, modified) in self.paths.iter().zip(self.modified.iter_mut()) {
+      let current = self::modified(path);
+      // Editors briefly remove files while saving, wait for them to come back
+      changed |= current.is_some() && current != *modified;
+      *modified = current;
+    }
+    if !changed {
+      return None;
+    }
+
+    let mut video = Video::new(settings);
+    match panic::catch_unwind(AssertUnwindSafe(|| (self.scene)(&mut video))) {
+      Ok(()) => {
+        log::info!("reloaded scene");
+        Some(video.into_scene())
+      }
+      Err(_) => {
+        log::error!("failed to rebuild scene, keeping the previous one");
+        None
+      }
+    }
+  }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+  path
+    .metadata()
+    .and_then(|metadata| metadata.modified())
+    .ok()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{cell::Cell, fs, rc::Rc};
+
+  use super::*;
+  use crate::api::rect::Rect;
+
+  /// A scene file in the temporary directory, removed when dropped
+  struct SceneFile(PathBuf);
+
+  impl SceneFile {
+    fn new(name: &str) -> Self {
+      let path = std::env::temp_dir().join(format!("vide-reload-{name}-{}", std::process::id()));
+      let file = Self(path);
+      file.write("ok", 0);
+      file
+    }
+
+    /// Writes `contents`, modified `seconds` after the epoch so changes don't
+    /// depend on the file system's timestamp resolution
+    fn write(&self, contents: &str, seconds: u64) {
+      fs::write(&self.0, contents).unwrap();
+      fs::File::options()
+        .write(true)
+        .open(&self.0)
+        .unwrap()
+        .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds))
+        .unwrap();
+    }
+  }
+
+  impl Drop for SceneFile {
+    fn drop(&mut self) {
+      let _ = fs::remove_file(&self.0);
+    }
+  }
+
+  /// Reloads a scene with one rect that panics when the file says so,
+  /// counting how often it was built
+  fn reload(file: &SceneFile) -> (Reload, Rc<Cell<u32>>) {
+    let builds = Rc::new(Cell::new(0));
+    let path = file.0.clone();
+    let scene = {
+      let builds = builds.clone();
+      move |video: &mut Video| {
+        builds.set(builds.get() + 1);
+        assert_ne!(fs::read_to_string(&path).unwrap(), "panic");
+        video.push_clip(Rect::builder().build());
+      }
+    };
+
+    (Reload::new(vec![file.0.clone()], Box::new(scene)), builds)
+  }
+
+  /// Polls as if `checks` intervals have passed
+  fn poll(reload: &mut Reload, checks: u32) -> Option<usize> {
+    let now = Instant::now() + Reload::INTERVAL * checks;
+    reload
+      .poll(now, VideoSettings::default())
+      .map(|(clips, _)| clips.len())
+  }
+
+  #[test]
+  fn changed_files_rebuild_the_scene() {
+    let file = SceneFile::new("changed");
+    let (mut reload, builds) = reload(&file);
+    assert_eq!(poll(&mut reload, 1), None);
+
+    file.write("ok", 10);
+    assert_eq!(poll(&mut reload, 2), Some(1));
+    assert_eq!(builds.get(), 1);
+    assert_eq!(poll(&mut reload, 3), None);
+  }
+
+  #[test]
+  fn checks_wait_for_the_interval() {
+    let file = SceneFile::new("interval");
+    let (mut reload, builds) = reload(&file);
+
+    file.write("ok", 10);
+    let checked = reload.checked;
+    assert!(reload.poll(checked, VideoSettings::default()).is_none());
+    assert_eq!(builds.get(), 0);
+  }
+
+  #[test]
+  fn missing_files_wait_until_they_are_back() {
+    let file = SceneFile::new("missing");
+    let (mut reload, builds) = reload(&file);
+
+    fs::remove_file(&file.0).unwrap();
+    assert_eq!(poll(&mut reload, 1), None);
+    assert_eq!(builds.get(), 0);
+
+    file.write("ok", 10);
+    assert_eq!(poll(&mut reload, 2), Some(1));
+  }
+
+  #[test]
+  fn panicking_scenes_keep_the_previous_one() {
+    let file = SceneFile::new("panicking");
+    let (mut reload, builds) = reload(&file);
+
+    file.write("panic", 10);
+    assert_eq!(poll(&mut reload, 1), None);
+    assert_eq!(builds.get(), 1);
+
+    file.write("ok", 20);
+    assert_eq!(poll(&mut reload, 2), Some(1));
+  }
+}
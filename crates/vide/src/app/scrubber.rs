@@ -0,0 +1,107 @@
+use super::playback::Playback;
+use crate::{api::rect::Rect, rgba8};
+
+/// Timeline overlay at the bottom of the preview window, showing the current
+/// frame and the loop range. Dragging the mouse across it seeks.
+///
+/// Sizes are in logical pixels and scale with the window's `scale` factor,
+/// positions are in physical window pixels.
+#[derive(Debug)]
+pub struct Scrubber {
+  pub visible: bool,
+  pub dragging: bool,
+}
+
+impl Default for Scrubber {
+  fn default() -> Self {
+    Self {
+      visible: true,
+      dragging: false,
+    }
+  }
+}
+
+impl Scrubber {
+  const BAR_HEIGHT: f32 = 6.0;
+  /// Height of the area that reacts to the mouse
+  const HEIGHT: f32 = 48.0;
+  /// Space around the bar
+  const MARGIN: f32 = 24.0;
+  const PLAYHEAD_SIZE: (f32, f32) = (4.0, 24.0);
+
+  /// Rects drawing the overlay, centered like all clips
+  pub fn rects(&self, playback: &Playback, window: (u32, u32), scale: f32) -> Vec<Rect> {
+    if !self.visible {
+      return vec![];
+    }
+
+    let (left, right) = Self::bar(window, scale);
+    let y = -(window.1 as f32) / 2.0 + Self::HEIGHT / 2.0 * scale;
+    let x_at = |frame: u64| left + (right - left) * frame as f32 / playback.frames as f32;
+    let span = |from: f32, to: f32, height: f32, color| {
+      Rect::builder()
+        .position(((from + to) / 2.0, y))
+        .size(((to - from).max(1.0), height * scale))
+        .color(color)
+        .rounded(0.5) // 50%
+        .build()
+    };
+
+    let loop_start = x_at(playback.loop_range.start);
+    let loop_end = x_at(playback.loop_range.end);
+    let playhead = x_at(playback.frame) + (right - left) / playback.frames as f32 / 2.0;
+    vec![
+      span(
+        left - Self::MARGIN / 2.0 * scale,
+        right + Self::MARGIN / 2.0 * scale,
+        Self::HEIGHT - Self::MARGIN / 2.0,
+        rgba8!(0x00, 0x00, 0x00, 0x80),
+      ),
+      span(
+        left,
+        right,
+        Self::BAR_HEIGHT,
+        rgba8!(0xff, 0xff, 0xff, 0x40),
+      ),
+      span(
+        loop_start,
+        loop_end,
+        Self::BAR_HEIGHT,
+        rgba8!(0xff, 0xc8, 0x00, 0x80),
+      ),
+      span(
+        loop_start,
+        playhead.max(loop_start),
+        Self::BAR_HEIGHT,
+        rgba8!(0xff, 0xc8, 0x00, 0xff),
+      ),
+      Rect::builder()
+        .position((playhead, y))
+        .size((Self::PLAYHEAD_SIZE.0 * scale, Self::PLAYHEAD_SIZE.1 * scale))
+        .color(rgba8!(0xff, 0xff, 0xff, 0xff))
+        .rounded(0.5) // 50%
+        .build(),
+    ]
+  }
+
+  /// Whether `point` is on the overlay
+  pub fn contains(&self, point: (f64, f64), window: (u32, u32), scale: f32) -> bool {
+    self.visible
+      && point.1 >= window.1 as f64 - (Self::HEIGHT * scale) as f64
+      && point.1 <= window.1 as f64
+  }
+
+  /// Frame under `x`
+  pub fn frame_at(&self, x: f64, playback: &Playback, window: (u32, u32), scale: f32) -> u64 {
+    let (left, right) = Self::bar(window, scale);
+    let x = x - window.0 as f64 / 2.0;
+    let progress = ((x - left as f64) / (right - left) as f64).clamp(0.0, 1.0);
+    ((progress * playback.frames as f64) as u64).min(playback.frames - 1)
+  }
+
+  /// Horizontal extent of the bar, centered
+  fn bar(window: (u32, u32), scale: f32) -> (f32, f32) {
+    let half = window.0 as f32 / 2.0 - Self::MARGIN * scale;
+    (-half, half)
+  }
+}
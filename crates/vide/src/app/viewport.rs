@@ -0,0 +1,215 @@
+use winit::keyboard::Key;
+
+/// Placement of the video in the preview window. By default the video is
+/// scaled to fit the window and letterboxed, zooming switches to a fixed
+/// scale that can be panned around. At 100% one pixel of the video is exactly
+/// one pixel of the window.
+///
+/// Positions are in physical window pixels from the top left corner.
+#[derive(Debug, Clone, Copy)]
+pub struct Viewport {
+  pub window: (u32, u32),
+  video: (u32, u32),
+  /// Window pixels per video pixel, `None` fits the video into the window
+  zoom: Option<f64>,
+  /// Offset of the video's center from the window's center
+  pan: (f64, f64),
+}
+
+impl Viewport {
+  /// Color around the video, linear
+  pub const LETTERBOX: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
+  const MAX_ZOOM: f64 = 32.0;
+  const MIN_ZOOM: f64 = 1.0 / 32.0;
+  /// Zoom factor per key press or scroll wheel notch
+  const ZOOM_STEP: f64 = 1.25;
+
+  pub fn new(video: (u32, u32), window: (u32, u32)) -> Self {
+    Self {
+      window,
+      video,
+      zoom: None,
+      pan: (0.0, 0.0),
+    }
+  }
+
+  /// Size for a new window showing `video`, as large as possible at 100% but
+  /// no more than 80% of the `monitor`
+  pub fn initial_window_size(video: (u32, u32), monitor: Option<(u32, u32)>) -> (u32, u32) {
+    let Some(monitor) = monitor else {
+      return video;
+    };
+
+    let scale = (monitor.0 as f64 * 0.8 / video.0 as f64)
+      .min(monitor.1 as f64 * 0.8 / video.1 as f64)
+      .min(1.0);
+    (
+      (video.0 as f64 * scale).round().max(1.0) as u32,
+      (video.1 as f64 * scale).round().max(1.0) as u32,
+    )
+  }
+
+  pub fn resize(&mut self, window: (u32, u32)) {
+    self.window = window;
+  }
+
+  /// Window pixels per video pixel
+  pub fn scale(&self) -> f64 {
+    self.zoom.unwrap_or_else(|| {
+      (self.window.0 as f64 / self.video.0 as f64).min(self.window.1 as f64 / self.video.1 as f64)
+    })
+  }
+
+  /// Position of the video's top left corner
+  pub fn offset(&self) -> (f64, f64) {
+    let scale = self.scale();
+    let offset = (
+      (self.window.0 as f64 - self.video.0 as f64 * scale) / 2.0 + self.pan.0,
+      (self.window.1 as f64 - self.video.1 as f64 * scale) / 2.0 + self.pan.1,
+    );
+
+    // Whole pixels keep a fixed zoom sharp, fitting stays centered
+    match self.zoom {
+      Some(_) => (offset.0.round(), offset.1.round()),
+      None => offset,
+    }
+  }
+
+  /// Position in video pixels of the window position `point`
+  pub fn video_position(&self, point: (f64, f64)) -> (f64, f64) {
+    let scale = self.scale();
+    let offset = self.offset();
+    ((point.0 - offset.0) / scale, (point.1 - offset.1) / scale)
+  }
+
+  pub fn fit(&mut self) {
+    self.zoom = None;
+    self.pan = (0.0, 0.0);
+  }
+
+  /// Shows the video pixel for pixel, centered
+  pub fn actual_size(&mut self) {
+    self.zoom = Some(1.0);
+    self.pan = (0.0, 0.0);
+  }
+
+  /// Zooms by `factor`, keeping what's under the window position `around` in
+  /// place
+  pub fn zoom_by(&mut self, factor: f64, around: (f64, f64)) {
+    let anchor = self.video_position(around);
+    let scale = (self.scale() * factor).clamp(Self::MIN_ZOOM, Self::MAX_ZOOM);
+    self.zoom = Some(scale);
+
+    // Pan so that `anchor` ends up at `around` again, `offset` then only
+    // rounds once
+    let centered = (
+      (self.window.0 as f64 - self.video.0 as f64 * scale) / 2.0,
+      (self.window.1 as f64 - self.video.1 as f64 * scale) / 2.0,
+    );
+    self.pan = (
+      around.0 - anchor.0 * scale - centered.0,
+      around.1 - anchor.1 * scale - centered.1,
+    );
+  }
+
+  /// Moves the video by `delta` window pixels
+  pub fn pan_by(&mut self, delta: (f64, f64)) {
+    self.zoom = Some(self.scale());
+    self.pan = (self.pan.0 + delta.0, self.pan.1 + delta.1);
+  }
+
+  /// Handles a pressed key, returns whether it was a viewport control:
+  ///
+  /// - `F`: fit the video into the window
+  /// - `Z`: zoom to 100%
+  /// - `+`/`-`: zoom in/out
+  pub fn handle_key(&mut self, key: &Key) -> bool {
+    let center = (self.window.0 as f64 / 2.0, self.window.1 as f64 / 2.0);
+    let Key::Character(character) = key else {
+      return false;
+    };
+
+    match character.to_lowercase().as_str() {
+      "f" => self.fit(),
+      "z" => self.actual_size(),
+      "+" | "=" => self.zoom_by(Self::ZOOM_STEP, center),
+      "-" => self.zoom_by(1.0 / Self::ZOOM_STEP, center),
+      _ => return false,
+    }
+
+    true
+  }
+
+  /// Zooms around `around` by `notches` scroll wheel notches, positive zooms
+  /// in
+  pub fn scroll(&mut self, notches: f64, around: (f64, f64)) {
+    self.zoom_by(Self::ZOOM_STEP.powf(notches), around);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn assert_near(actual: (f64, f64), expected: (f64, f64), tolerance: f64) {
+    assert!(
+      (actual.0 - expected.0).abs() <= tolerance && (actual.1 - expected.1).abs() <= tolerance,
+      "{actual:?} isn't within {tolerance} of {expected:?}",
+    );
+  }
+
+  #[test]
+  fn zooming_keeps_the_anchor_in_place() {
+    let mut viewport = Viewport::new((1920, 1080), (1280, 720));
+    let around = (301.0, 199.0);
+
+    for factor in [1.25, 1.25, 3.0, 0.5, 0.1, 7.0] {
+      let anchor = viewport.video_position(around);
+      viewport.zoom_by(factor, around);
+      // Rounding the offset to whole pixels moves it by half a pixel at most
+      let moved = viewport.video_position(around);
+      assert_near(moved, anchor, 0.5 / viewport.scale() + 1e-9);
+    }
+  }
+
+  #[test]
+  fn zoom_is_clamped() {
+    let mut viewport = Viewport::new((1920, 1080), (1280, 720));
+    viewport.zoom_by(1000.0, (0.0, 0.0));
+    assert_eq!(viewport.scale(), Viewport::MAX_ZOOM);
+
+    viewport.zoom_by(1e-6, (0.0, 0.0));
+    assert_eq!(viewport.scale(), Viewport::MIN_ZOOM);
+  }
+
+  #[test]
+  fn actual_size_lands_on_whole_pixels() {
+    let mut viewport = Viewport::new((1920, 1080), (1001, 777));
+    viewport.actual_size();
+
+    assert_eq!(viewport.scale(), 1.0);
+    let offset = viewport.offset();
+    assert_eq!(offset, (offset.0.round(), offset.1.round()));
+    assert_near(offset, (-459.5, -151.5), 0.5);
+  }
+
+  #[test]
+  fn fitting_letterboxes_the_video() {
+    let mut viewport = Viewport::new((1920, 1080), (1000, 1000));
+    viewport.zoom_by(2.0, (100.0, 100.0));
+    viewport.fit();
+
+    assert_eq!(viewport.scale(), 1000.0 / 1920.0);
+    assert_near(viewport.offset(), (0.0, 218.75), 1e-9);
+  }
+
+  #[test]
+  fn windows_take_up_to_80_percent_of_the_monitor() {
+    let size = Viewport::initial_window_size;
+    assert_eq!(size((1920, 1080), Some((1920, 1080))), (1536, 864));
+    assert_eq!(size((1080, 1920), Some((2560, 1440))), (648, 1152));
+    // Small videos aren't scaled up
+    assert_eq!(size((640, 360), Some((2560, 1440))), (640, 360));
+    assert_eq!(size((1920, 1080), None), (1920, 1080));
+  }
+}
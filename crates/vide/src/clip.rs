@@ -1,6 +1,6 @@
-use std::{sync::MutexGuard, time::Duration};
+use std::{sync::Mutex, time::Duration};
 
-use crate::render::Renderer;
+use crate::render::{Renderer, Time};
 
 pub trait IntoFrame {
   fn into_frame(self, fps: f64) -> u64;
@@ -24,12 +24,16 @@ impl IntoFrame for f64 {
   }
 }
 
+/// Something drawn for a part of its parent's timeline. Placement is in
+/// frames of the parent, counted at `fps` frames per second
 pub trait Clip {
-  fn start(&self) -> u64;
+  fn start(&self, fps: f64) -> u64;
 
-  fn end(&self, video_end: u64) -> u64;
+  fn end(&self, video_end: u64, fps: f64) -> u64;
 
-  fn in_time_frame(&self, frame: u64) -> bool;
+  fn in_time_frame(&self, frame: u64, fps: f64) -> bool;
 
-  fn render(&mut self, renderer: &mut Renderer, pass: MutexGuard<wgpu::RenderPass<'_>>, frame: u64);
+  /// Draws the clip into `pass`, `time` holds the current timing relative to
+  /// the video, the parent sequence and this clip
+  fn render(&mut self, renderer: &mut Renderer, pass: &Mutex<wgpu::RenderPass<'_>>, time: Time);
 }
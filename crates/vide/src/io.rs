@@ -2,7 +2,142 @@ use crate::api::video::VideoSettings;
 
 pub trait Export {
   fn begin(&mut self, settings: VideoSettings);
-  /// `frame` contains Rgba8UnormSrgb data as bytes (RGBA8)
+  /// `frame` contains Rgba8UnormSrgb data as bytes (RGBA8), row by row without
+  /// padding. Colors are srgb encoded with premultiplied alpha, so dropping
+  /// alpha is the same as compositing the frame over black. Exporters
+  /// converting to YUV can use [`rgba_to_nv12`] and should tag the video as
+  /// BT.709 (primaries, transfer function and matrix)
   fn push_frame(&mut self, keyframe: bool, frame: &[u8]);
   fn end(self);
 }
+
+/// Range of the values in a YUV frame
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ColorRange {
+  /// Luma from 16 to 235 and chroma from 16 to 240, what video players expect
+  /// unless told otherwise
+  #[default]
+  Limited,
+  /// Luma and chroma use the whole range from 0 to 255
+  Full,
+}
+
+/// Converts a frame passed to [`Export::push_frame`] to YUV 4:2:0 using the
+/// BT.709 matrix. Returns the luma plane with one byte per pixel and the
+/// chroma plane with interleaved U and V bytes for every 2x2 block of pixels
+/// (NV12). Alpha is dropped, see [`Export::push_frame`]
+pub fn rgba_to_nv12(
+  frame: &[u8],
+  width: usize,
+  height: usize,
+  range: ColorRange,
+) -> (Vec<u8>, Vec<u8>) {
+  const KR: f32 = 0.2126;
+  const KB: f32 = 0.0722;
+
+  let (luma_scale, luma_offset, chroma_scale) = match range {
+    ColorRange::Limited => (219.0, 16.0, 224.0),
+    ColorRange::Full => (255.0, 0.0, 255.0),
+  };
+
+  let pixel = |x: usize, y: usize| {
+    let index = (y.min(height - 1) * width + x.min(width - 1)) * 4;
+    [0, 1, 2].map(|c| frame[index + c] as f32 / 255.0)
+  };
+  let luma = |[r, g, b]: [f32; 3]| KR * r + (1.0 - KR - KB) * g + KB * b;
+
+  let mut y_plane = Vec::with_capacity(width * height);
+  for y in 0..height {
+    for x in 0..width {
+      let value = luma(pixel(x, y)) * luma_scale + luma_offset;
+      y_plane.push(value.round().clamp(0.0, 255.0) as u8);
+    }
+  }
+
+  let (chroma_width, chroma_height) = (width.div_ceil(2), height.div_ceil(2));
+  let mut uv_plane = Vec::with_capacity(chroma_width * chroma_height * 2);
+  for y in 0..chroma_height {
+    for x in 0..chroma_width {
+      // Average of the 2x2 block, edges repeat the last row or column
+      let mut rgb = [0.0; 3];
+      for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+        let sample = pixel(x * 2 + dx, y * 2 + dy);
+        rgb = [0, 1, 2].map(|c| rgb[c] + sample[c] / 4.0);
+      }
+
+      let luma = luma(rgb);
+      let u = (rgb[2] - luma) / (2.0 * (1.0 - KB));
+      let v = (rgb[0] - luma) / (2.0 * (1.0 - KR));
+      for chroma in [u, v] {
+        let value = chroma * chroma_scale + 128.0;
+        uv_plane.push(value.round().clamp(0.0, 255.0) as u8);
+      }
+    }
+  }
+
+  (y_plane, uv_plane)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// A frame filled with one opaque color
+  fn solid(width: usize, height: usize, rgb: [u8; 3]) -> Vec<u8> {
+    [rgb[0], rgb[1], rgb[2], 255].repeat(width * height)
+  }
+
+  #[test]
+  fn limited_range_maps_black_and_white_to_16_and_235() {
+    let (y, uv) = rgba_to_nv12(&solid(2, 2, [0; 3]), 2, 2, ColorRange::Limited);
+    assert_eq!((y, uv), (vec![16; 4], vec![128; 2]));
+
+    let (y, uv) = rgba_to_nv12(&solid(2, 2, [255; 3]), 2, 2, ColorRange::Limited);
+    assert_eq!((y, uv), (vec![235; 4], vec![128; 2]));
+  }
+
+  #[test]
+  fn full_range_uses_every_value() {
+    let (y, _) = rgba_to_nv12(&solid(2, 2, [0; 3]), 2, 2, ColorRange::Full);
+    assert_eq!(y, vec![0; 4]);
+
+    let (y, _) = rgba_to_nv12(&solid(2, 2, [255; 3]), 2, 2, ColorRange::Full);
+    assert_eq!(y, vec![255; 4]);
+  }
+
+  #[test]
+  fn greys_have_neutral_chroma() {
+    for range in [ColorRange::Limited, ColorRange::Full] {
+      let (_, uv) = rgba_to_nv12(&solid(4, 2, [90; 3]), 4, 2, range);
+      assert_eq!(uv, vec![128; 4]);
+    }
+
+    // Blue pushes U up and V down, red the other way around
+    let (_, uv) = rgba_to_nv12(&solid(2, 2, [0, 0, 255]), 2, 2, ColorRange::Full);
+    assert!(uv[0] > 128 && uv[1] < 128);
+    let (_, uv) = rgba_to_nv12(&solid(2, 2, [255, 0, 0]), 2, 2, ColorRange::Full);
+    assert!(uv[0] < 128 && uv[1] > 128);
+  }
+
+  #[test]
+  fn odd_sizes_repeat_the_last_row_and_column() {
+    // White on the left, black in the last column
+    let mut frame = solid(3, 3, [255; 3]);
+    for y in 0..3 {
+      frame[(y * 3 + 2) * 4..(y * 3 + 2) * 4 + 3].fill(0);
+    }
+
+    let (y, uv) = rgba_to_nv12(&frame, 3, 3, ColorRange::Full);
+    assert_eq!(y, [255, 255, 0].repeat(3));
+    // 2x2 chroma blocks, the right ones only cover black pixels
+    assert_eq!(uv, vec![128; 8]);
+  }
+
+  #[test]
+  fn single_pixels_get_their_own_chroma() {
+    let (y, uv) = rgba_to_nv12(&solid(1, 1, [255, 0, 0]), 1, 1, ColorRange::Limited);
+    assert_eq!(y.len(), 1);
+    assert_eq!(uv.len(), 2);
+    assert_eq!(y[0], (0.2126f32 * 219.0 + 16.0).round() as u8);
+  }
+}
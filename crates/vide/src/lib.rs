@@ -2,7 +2,6 @@
 
 // TODO: Add docs for these modules
 
-#[cfg(feature = "preview")]
 mod app;
 
 pub mod api;
@@ -18,12 +17,28 @@ pub use paste;
 pub mod prelude {
   pub use super::{
     api::{
-      animation::{ease, Animated, AnimatedBuilder, KeyframeTiming::*},
+      animation::{
+        ease, Additive, Animated, AnimatedBuilder, Blend, Interpolate, Interpolation,
+        KeyframeTiming::*, Repeat, Spring,
+      },
+      choreography::{Placed, Stagger, StaggerOrigin, Timed, Timeline},
       color::*,
+      effect::{
+        bloom::Bloom, blur::GaussianBlur, chromatic_aberration::ChromaticAberration,
+        color_adjust::ColorAdjust, vignette::Vignette, Effect, Layer,
+      },
+      matte::{MatteMode, TrackMatte},
+      noise::{Noise, NoiseKind},
+      path::Path,
       rect::Rect,
+      sequence::Sequence,
+      shader_clip::ShaderClip,
+      time_remap::TimeRemap,
       transform::Transform,
       video::*,
     },
-    cubic_bezier, lerp, rgb8, rgba8, unanimated,
+    cubic_bezier, lerp,
+    render::Time,
+    rgb8, rgba8, unanimated,
   };
 }
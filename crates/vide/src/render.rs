@@ -1,18 +1,23 @@
-use std::{
-  sync::{Arc, Mutex},
-  time::Duration,
-};
+use std::{collections::VecDeque, sync::Mutex, time::Duration};
 
 use wgpu::util::DeviceExt;
 
+pub use self::{export::ExportOutput, window::WindowOutput};
 use crate::{
-  api::video::VideoSettings,
+  api::{effect::EffectStack, video::VideoSettings},
   clip::{Clip, IntoFrame},
 };
 
+mod export;
+mod window;
+
 /// Timing information needed for rendering
 #[derive(Default, Debug, Clone, Copy)]
 pub struct Time {
+  /// Frames per second of the video, every frame below is counted at this
+  /// rate
+  pub fps: f64,
+
   /// Current frame
   pub video_frame: u64,
   /// Current frame relative to the first frame of the parent sequence
@@ -38,13 +43,72 @@ pub struct Time {
   /// Current clip progress ranging from `0.0` to `1.0`. This can be used as
   /// `time` input for interpolation functions
   pub clip_progress: f64,
+
+  /// Length of the parent sequence in frames, clips with an infinite end
+  /// last until here
+  pub sequence_frames: u64,
+  /// Length of this clip in frames, `clip_progress` reaches `1.0` at its end
+  pub clip_frames: u64,
 }
 
 impl Time {
+  /// Timing of `video_frame` in a video that is `video_frames` long. The video
+  /// acts as its own sequence and clip
+  pub fn new(video_frame: u64, video_frames: u64, fps: f64) -> Self {
+    let video_time = video_frame as f64 / fps;
+    let video_progress = progress(video_frame, video_frames);
+
+    Self {
+      fps,
+      video_frame,
+      sequence_frame: video_frame,
+      clip_frame: video_frame,
+      video_time,
+      sequence_time: video_time,
+      clip_time: video_time,
+      video_progress,
+      sequence_progress: video_progress,
+      clip_progress: video_progress,
+      sequence_frames: video_frames,
+      clip_frames: video_frames,
+    }
+  }
+
+  /// Timing of a clip placed inside the current clip, spanning the frames
+  /// `start..end` of it. The current clip becomes the parent sequence
+  pub fn child(self, start: u64, end: u64) -> Self {
+    let clip_frame = self.clip_frame.saturating_sub(start);
+    let clip_frames = end.saturating_sub(start);
+    let mut time = self.derive_clip(
+      clip_frame,
+      clip_frame as f64 / self.fps,
+      progress(clip_frame, clip_frames),
+    );
+    time.clip_frames = clip_frames;
+
+    time
+  }
+
+  /// Timing of a clip placed next to the current clip, spanning the frames
+  /// `start..end` of the parent sequence
+  pub fn sibling(mut self, start: u64, end: u64) -> Self {
+    let clip_frame = self.sequence_frame.saturating_sub(start);
+    self.clip_frame = clip_frame;
+    self.clip_time = clip_frame as f64 / self.fps;
+    self.clip_frames = end.saturating_sub(start);
+    self.clip_progress = progress(clip_frame, self.clip_frames);
+
+    self
+  }
+
+  /// Timing of a clip that shows this clip at different frames, e.g. remapped
+  /// in time. The current clip becomes the parent sequence, the length stays
+  /// the same
   pub fn derive_clip(mut self, clip_frame: u64, clip_time: f64, clip_progress: f64) -> Self {
     self.sequence_frame = self.clip_frame;
     self.sequence_time = self.clip_time;
     self.sequence_progress = self.clip_progress;
+    self.sequence_frames = self.clip_frames;
     self.clip_frame = clip_frame;
     self.clip_time = clip_time;
     self.clip_progress = clip_progress;
@@ -53,6 +117,15 @@ impl Time {
   }
 }
 
+#[inline]
+pub(crate) fn progress(frame: u64, frames: u64) -> f64 {
+  if frames == 0 {
+    0.0
+  } else {
+    frame as f64 / frames as f64
+  }
+}
+
 pub enum RenderEvent<'a> {
   WriteBuffer {
     buffer: &'a wgpu::Buffer,
@@ -62,58 +135,104 @@ pub enum RenderEvent<'a> {
   SetTransform(cgmath::Matrix4<f32>),
   Clip {
     clip: &'a mut dyn Clip,
-    frame: u64,
+    time: Time,
+  },
+}
+
+/// Events rendering the clips visible at `time`, a frame of a video that is
+/// `video_frames` long
+pub(crate) fn clip_events(
+  clips: &mut VecDeque<Box<dyn Clip>>,
+  time: Time,
+  video_frames: u64,
+) -> Vec<RenderEvent<'_>> {
+  clips
+    .iter_mut()
+    .filter(|clip| clip.in_time_frame(time.video_frame, time.fps))
+    .map(|clip| {
+      let time = time.child(clip.start(time.fps), clip.end(video_frames, time.fps));
+      RenderEvent::Clip {
+        clip: clip.as_mut(),
+        time,
+      }
+    })
+    .collect()
+}
+
+/// Offscreen color and depth attachments with the same size and format as the
+/// video output. Clips that need to post-process their content (mattes,
+/// effects, ...) render into these and composite the result afterwards
+#[derive(Debug)]
+pub struct RenderTarget {
+  texture: wgpu::Texture,
+  view: wgpu::TextureView,
+  depth_view: wgpu::TextureView,
+}
+
+impl RenderTarget {
+  #[inline]
+  pub fn texture(&self) -> &wgpu::Texture {
+    &self.texture
+  }
+
+  #[inline]
+  pub fn view(&self) -> &wgpu::TextureView {
+    &self.view
+  }
+}
+
+/// Blends colors with straight alpha into frames and render targets, which
+/// hold premultiplied colors. Pipelines whose shaders output premultiplied
+/// colors use [`wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING`] instead
+pub const STRAIGHT_ALPHA_BLENDING: wgpu::BlendState = wgpu::BlendState {
+  color: wgpu::BlendComponent {
+    src_factor: wgpu::BlendFactor::SrcAlpha,
+    dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+    operation: wgpu::BlendOperation::Add,
   },
+  alpha: wgpu::BlendComponent::OVER,
+};
+
+/// Destination of rendered frames, like a file being exported or a preview
+/// window. After every frame the [`Renderer`] rendered, the output takes it
+/// from [`Renderer::frame`]
+pub trait Output {
+  /// Takes the frame that was just rendered. `overlay` is drawn on top of it
+  /// by outputs that show it to the user and ignored by the others
+  fn present(&mut self, renderer: &mut Renderer, overlay: Vec<RenderEvent>);
 }
 
+/// Renders frames of a video at its output resolution. Where they end up is
+/// up to an [`Output`], so the same renderer previews and exports
 pub struct Renderer {
   pub settings: VideoSettings,
   pub screen_matrix: cgmath::Matrix4<f32>,
 
   // WGPU Special
+  instance: wgpu::Instance,
+  adapter: wgpu::Adapter,
   queue: wgpu::Queue,
   device: wgpu::Device,
-  config: wgpu::SurfaceConfiguration,
-
-  // VRAM -> RAM transfer for exporting to file
-  #[cfg(not(feature = "preview"))]
-  out_texture: wgpu::Texture,
-  #[cfg(not(feature = "preview"))]
-  out_texture_view: wgpu::TextureView,
-  #[cfg(not(feature = "preview"))]
-  unpadded_bytes_per_row: u32,
-  #[cfg(not(feature = "preview"))]
-  padded_bytes_per_row: u32,
-  #[cfg(not(feature = "preview"))]
-  out_buffer: wgpu::Buffer,
-
-  // Window surface for preview
-  #[cfg(feature = "preview")]
-  surface: wgpu::Surface<'static>,
+
+  /// Every frame is rendered into this, outputs take it from here
+  frame: RenderTarget,
 
   transform_buffer: wgpu::Buffer,
   transform_bind_group_layout: wgpu::BindGroupLayout,
   transform_bind_group: wgpu::BindGroup,
-
-  depth_texture_view: wgpu::TextureView,
 }
 
 impl Renderer {
-  pub fn new(
-    settings: VideoSettings,
-    #[cfg(feature = "preview")] window: Arc<winit::window::Window>,
-  ) -> Self {
-    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+  /// Format of frames and render targets. Colors are rendered in linear light
+  /// and encoded to srgb when stored
+  pub const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
 
-    #[cfg(feature = "preview")]
-    let surface = instance.create_surface(window).unwrap();
+  pub fn new(settings: VideoSettings) -> Self {
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
 
     let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
       power_preference: wgpu::PowerPreference::HighPerformance,
       force_fallback_adapter: false,
-      #[cfg(feature = "preview")]
-      compatible_surface: Some(&surface),
-      #[cfg(not(feature = "preview"))]
       compatible_surface: None,
     }))
     .unwrap();
@@ -133,84 +252,7 @@ impl Renderer {
     ))
     .unwrap();
 
-    #[cfg(not(feature = "preview"))]
-    let (out_texture, out_texture_view, unpadded_bytes_per_row, padded_bytes_per_row, out_buffer) = {
-      let out_texture = device.create_texture(&wgpu::TextureDescriptor {
-        label: Some("Output Texture"),
-        size: wgpu::Extent3d {
-          width: settings.resolution.0,
-          height: settings.resolution.1,
-          depth_or_array_layers: 1,
-        },
-        mip_level_count: 1,
-        sample_count: 1,
-        dimension: wgpu::TextureDimension::D2,
-        format: wgpu::TextureFormat::Rgba8UnormSrgb,
-        usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::RENDER_ATTACHMENT,
-        view_formats: &[],
-      });
-      let out_texture_view = out_texture.create_view(&wgpu::TextureViewDescriptor::default());
-
-      let pixel_size = std::mem::size_of::<[u8; 4]>() as u32;
-      let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
-      let unpadded_bytes_per_row = pixel_size * settings.resolution.0;
-      let padding = (align - unpadded_bytes_per_row % align) % align;
-      let padded_bytes_per_row = unpadded_bytes_per_row + padding;
-
-      let buffer_size = (padded_bytes_per_row * settings.resolution.1) as wgpu::BufferAddress;
-      let out_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-        label: Some("Output Buffer"),
-        size: buffer_size,
-        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
-        mapped_at_creation: false,
-      });
-
-      (
-        out_texture,
-        out_texture_view,
-        unpadded_bytes_per_row,
-        padded_bytes_per_row,
-        out_buffer,
-      )
-    };
-
-    let config = {
-      #[cfg(feature = "preview")]
-      let capabilities = surface.get_capabilities(&adapter);
-
-      let config = wgpu::SurfaceConfiguration {
-        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-        #[cfg(feature = "preview")]
-        format: capabilities.formats[0],
-        #[cfg(not(feature = "preview"))]
-        format: wgpu::TextureFormat::Rgba8UnormSrgb,
-        width: settings.resolution.0,
-        height: settings.resolution.1,
-        #[cfg(feature = "preview")]
-        present_mode: wgpu::PresentMode::Fifo,
-        #[cfg(not(feature = "preview"))]
-        present_mode: wgpu::PresentMode::Immediate,
-        #[cfg(feature = "preview")]
-        alpha_mode: capabilities.alpha_modes[0],
-        #[cfg(not(feature = "preview"))]
-        alpha_mode: wgpu::CompositeAlphaMode::Auto,
-        desired_maximum_frame_latency: 2,
-        view_formats: vec![],
-      };
-
-      #[cfg(feature = "preview")]
-      surface.configure(&device, &config);
-
-      config
-    };
-
-    #[rustfmt::skip]
-    let screen_matrix = cgmath::Matrix4::new(
-      2.0 / settings.resolution.0 as f32, 0.0,                                0.0, 0.0,
-      0.0,                                2.0 / settings.resolution.1 as f32, 0.0, 0.0,
-      0.0,                                0.0,                                1.0, 0.0,
-      0.0,                                0.0,                                0.0, 1.0,
-    );
+    let screen_matrix = screen_matrix(settings.resolution);
 
     let transform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
       label: Some("Transform Buffer"),
@@ -242,50 +284,22 @@ impl Renderer {
       }],
     });
 
-    let depth_texture = device.create_texture(&wgpu::TextureDescriptor {
-      label: Some("Depth Texture"),
-      size: wgpu::Extent3d {
-        width: settings.resolution.0,
-        height: settings.resolution.1,
-        depth_or_array_layers: 1,
-      },
-      mip_level_count: 1,
-      sample_count: 1,
-      dimension: wgpu::TextureDimension::D2,
-      format: wgpu::TextureFormat::Depth32Float,
-      usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
-      view_formats: &[],
-    });
-
-    let depth_texture_view = depth_texture.create_view(&wgpu::TextureViewDescriptor::default());
+    let frame = render_target(&device, settings.resolution);
 
     Self {
       settings,
       screen_matrix,
 
+      instance,
+      adapter,
       queue,
       device,
-      config,
-
-      #[cfg(not(feature = "preview"))]
-      out_texture,
-      #[cfg(not(feature = "preview"))]
-      out_texture_view,
-      #[cfg(not(feature = "preview"))]
-      unpadded_bytes_per_row,
-      #[cfg(not(feature = "preview"))]
-      padded_bytes_per_row,
-      #[cfg(not(feature = "preview"))]
-      out_buffer,
-
-      #[cfg(feature = "preview")]
-      surface,
+
+      frame,
 
       transform_buffer,
       transform_bind_group_layout,
       transform_bind_group,
-
-      depth_texture_view,
     }
   }
 
@@ -314,9 +328,10 @@ impl Renderer {
     &self.device
   }
 
+  /// Format pipelines drawing into frames or render targets have to use
   #[inline]
-  pub fn wgpu_config(&self) -> wgpu::SurfaceConfiguration {
-    self.config.clone()
+  pub fn wgpu_format(&self) -> wgpu::TextureFormat {
+    Self::FORMAT
   }
 
   #[inline]
@@ -324,43 +339,42 @@ impl Renderer {
     &self.transform_bind_group_layout
   }
 
-  pub(crate) fn render(&mut self, events: Vec<RenderEvent>) -> Option<Vec<u8>> {
+  /// The last rendered frame
+  #[inline]
+  pub fn frame(&self) -> &RenderTarget {
+    &self.frame
+  }
+
+  pub fn create_render_target(&self) -> RenderTarget {
+    render_target(&self.device, self.settings.resolution)
+  }
+
+  /// Clears `target` to `clear` and records `render` into a render pass
+  /// drawing to it. The commands are submitted right away, so the target can
+  /// be sampled by anything drawn afterwards in the current frame
+  pub fn render_to_target<F>(&mut self, target: &RenderTarget, clear: wgpu::Color, render: F)
+  where
+    F: FnOnce(&mut Renderer, &Mutex<wgpu::RenderPass<'_>>),
+  {
     let mut encoder = self
       .device
       .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-        label: Some("Main Command Encoder"),
+        label: Some("Offscreen Command Encoder"),
       });
 
-    #[cfg(feature = "preview")]
-    let (output, surface_view) = {
-      let output = self.surface.get_current_texture().unwrap();
-      let view = output
-        .texture
-        .create_view(&wgpu::TextureViewDescriptor::default());
-      (output, view)
-    };
-
     {
       let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-        label: Some("Render Pass"),
+        label: Some("Offscreen Render Pass"),
         color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-          #[cfg(not(feature = "preview"))]
-          view: &self.out_texture_view,
-          #[cfg(feature = "preview")]
-          view: &surface_view,
+          view: &target.view,
           resolve_target: None,
           ops: wgpu::Operations {
-            load: wgpu::LoadOp::Clear(wgpu::Color {
-              r: self.settings.background_color.r,
-              g: self.settings.background_color.g,
-              b: self.settings.background_color.b,
-              a: 1.0,
-            }),
+            load: wgpu::LoadOp::Clear(clear),
             store: wgpu::StoreOp::Store,
           },
         })],
         depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
-          view: &self.depth_texture_view,
+          view: &target.depth_view,
           depth_ops: Some(wgpu::Operations {
             load: wgpu::LoadOp::Clear(1.0),
             store: wgpu::StoreOp::Store,
@@ -373,82 +387,150 @@ impl Renderer {
       pass.set_bind_group(0, &self.transform_bind_group, &[]);
 
       let pass_ref = Mutex::new(pass);
-      for event in events {
-        match event {
-          RenderEvent::WriteBuffer {
-            buffer,
-            offset,
-            data,
-          } => {
-            self.queue.write_buffer(buffer, offset, data);
-          }
-          RenderEvent::SetTransform(transform) => {
-            self.queue.write_buffer(
-              &self.transform_buffer,
-              0,
-              bytemuck::cast_slice(&[Into::<[[f32; 4]; 4]>::into(transform)]),
-            );
-          }
-          RenderEvent::Clip { clip, frame } => {
-            clip.render(self, pass_ref.lock().unwrap(), frame);
-          }
+      render(self, &pass_ref);
+    }
+
+    self.queue.submit(std::iter::once(encoder.finish()));
+  }
+
+  /// Clear color of every frame, premultiplied and in linear light like
+  /// everything else that is rendered. The frame encodes it to srgb
+  #[inline]
+  fn background(&self) -> wgpu::Color {
+    let [r, g, b, a] = self.settings.background_color.premultiplied();
+    wgpu::Color { r, g, b, a }
+  }
+
+  pub(crate) fn render_events(
+    &mut self,
+    pass: &Mutex<wgpu::RenderPass<'_>>,
+    events: Vec<RenderEvent>,
+  ) {
+    for event in events {
+      match event {
+        RenderEvent::WriteBuffer {
+          buffer,
+          offset,
+          data,
+        } => {
+          self.queue.write_buffer(buffer, offset, data);
+        }
+        RenderEvent::SetTransform(transform) => {
+          self.queue.write_buffer(
+            &self.transform_buffer,
+            0,
+            bytemuck::cast_slice(&[Into::<[[f32; 4]; 4]>::into(transform)]),
+          );
+        }
+        RenderEvent::Clip { clip, time } => {
+          clip.render(self, pass, time);
         }
       }
     }
+  }
 
-    #[cfg(not(feature = "preview"))]
-    encoder.copy_texture_to_buffer(
-      wgpu::TexelCopyTextureInfo {
-        aspect: wgpu::TextureAspect::All,
-        texture: &self.out_texture,
-        mip_level: 0,
-        origin: wgpu::Origin3d::ZERO,
-      },
-      wgpu::TexelCopyBufferInfo {
-        buffer: &self.out_buffer,
-        layout: wgpu::TexelCopyBufferLayout {
-          offset: 0,
-          bytes_per_row: Some(self.padded_bytes_per_row),
-          rows_per_image: Some(self.settings.resolution.1),
-        },
-      },
-      wgpu::Extent3d {
-        width: self.settings.resolution.0,
-        height: self.settings.resolution.1,
-        depth_or_array_layers: 1,
-      },
-    );
-
-    self.queue.submit(std::iter::once(encoder.finish()));
-    #[cfg(feature = "preview")]
-    output.present();
-    #[cfg(feature = "preview")]
-    return None;
+  /// Renders one frame of the video into [`Renderer::frame`]. `effects` are
+  /// applied to the whole frame and `time` is the timing of the video, used
+  /// to evaluate their parameters
+  pub(crate) fn render(&mut self, events: Vec<RenderEvent>, effects: &mut EffectStack, time: Time) {
+    let mut encoder = self
+      .device
+      .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Main Command Encoder"),
+      });
 
-    #[cfg(not(feature = "preview"))]
     {
-      let buffer_slice = self.out_buffer.slice(..);
-      let (tx, rx) = futures_intrusive::channel::shared::oneshot_channel();
-      buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
-        tx.send(result).unwrap();
+      let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("Render Pass"),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+          view: &self.frame.view,
+          resolve_target: None,
+          ops: wgpu::Operations {
+            load: wgpu::LoadOp::Clear(self.background()),
+            store: wgpu::StoreOp::Store,
+          },
+        })],
+        depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+          view: &self.frame.depth_view,
+          depth_ops: Some(wgpu::Operations {
+            load: wgpu::LoadOp::Clear(1.0),
+            store: wgpu::StoreOp::Store,
+          }),
+          stencil_ops: None,
+        }),
+        ..Default::default()
       });
-      self.device.poll(wgpu::Maintain::Wait);
-      let result = pollster::block_on(rx.receive()).unwrap();
-
-      match result {
-        Ok(()) => {
-          let padded_data = buffer_slice.get_mapped_range();
-          let data = padded_data
-            .chunks(self.padded_bytes_per_row as _)
-            .flat_map(|chunk| &chunk[..self.unpadded_bytes_per_row as _])
-            .copied()
-            .collect::<Vec<_>>();
-          drop(padded_data);
-          self.out_buffer.unmap();
-          Some(data)
-        }
-        _ => panic!("Something went wrong while copying GPU buffer to RAM for encoding!"),
+
+      pass.set_bind_group(0, &self.transform_bind_group, &[]);
+
+      let pass_ref = Mutex::new(pass);
+      if effects.is_empty() {
+        self.render_events(&pass_ref, events);
+      } else {
+        // Effects apply to the whole frame, background included
+        let background = self.background();
+        effects.render(
+          self,
+          pass_ref.lock().unwrap(),
+          time,
+          background,
+          |renderer, pass| renderer.render_events(pass, events),
+        );
       }
     }
+
+    self.queue.submit(std::iter::once(encoder.finish()));
+  }
+}
+
+/// Maps pixels, centered on the target, to clip space
+fn screen_matrix(size: (u32, u32)) -> cgmath::Matrix4<f32> {
+  #[rustfmt::skip]
+  let matrix = cgmath::Matrix4::new(
+    2.0 / size.0 as f32, 0.0,                 0.0, 0.0,
+    0.0,                 2.0 / size.1 as f32, 0.0, 0.0,
+    0.0,                 0.0,                 1.0, 0.0,
+    0.0,                 0.0,                 0.0, 1.0,
+  );
+  matrix
+}
+
+fn render_target(device: &wgpu::Device, size: (u32, u32)) -> RenderTarget {
+  let size = wgpu::Extent3d {
+    width: size.0,
+    height: size.1,
+    depth_or_array_layers: 1,
+  };
+
+  let texture = device.create_texture(&wgpu::TextureDescriptor {
+    label: Some("Render Target Texture"),
+    size,
+    mip_level_count: 1,
+    sample_count: 1,
+    dimension: wgpu::TextureDimension::D2,
+    format: Renderer::FORMAT,
+    usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+      | wgpu::TextureUsages::TEXTURE_BINDING
+      | wgpu::TextureUsages::COPY_SRC,
+    view_formats: &[],
+  });
+  let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+  let depth_texture = device.create_texture(&wgpu::TextureDescriptor {
+    label: Some("Render Target Depth Texture"),
+    size,
+    mip_level_count: 1,
+    sample_count: 1,
+    dimension: wgpu::TextureDimension::D2,
+    format: wgpu::TextureFormat::Depth32Float,
+    usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+    view_formats: &[],
+  });
+  let depth_view = depth_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+  RenderTarget {
+    texture,
+    view,
+    depth_view,
   }
 }
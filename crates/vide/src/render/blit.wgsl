@@ -0,0 +1,26 @@
+// `const ENCODE_SRGB: bool` is prepended, set for window formats that don't
+// encode to srgb themselves
+
+@group(1)
+@binding(1)
+var input_sampler: sampler;
+
+@group(1)
+@binding(2)
+var input_texture: texture_2d<f32>;
+
+fn linear_to_srgb(linear: vec3<f32>) -> vec3<f32> {
+  let low = linear * 12.92;
+  let high = 1.055 * pow(linear, vec3(1.0 / 2.4)) - 0.055;
+  return select(high, low, linear <= vec3(0.0031308));
+}
+
+@fragment
+fn fs_main(in: FullscreenOutput) -> @location(0) vec4<f32> {
+  let color = textureSample(input_texture, input_sampler, in.uv);
+  if ENCODE_SRGB {
+    return vec4(linear_to_srgb(max(color.rgb, vec3(0.0))), color.a);
+  }
+
+  return color;
+}
@@ -0,0 +1,106 @@
+use super::{Output, RenderEvent, Renderer};
+use crate::io::Export;
+
+/// Copies every frame from VRAM to RAM and passes it on to an [`Export`]
+/// for encoding. Overlays are ignored, they never end up in the file
+pub struct ExportOutput<E: Export> {
+  exporter: E,
+  buffer: wgpu::Buffer,
+  unpadded_bytes_per_row: u32,
+  padded_bytes_per_row: u32,
+}
+
+impl<E: Export> ExportOutput<E> {
+  /// Begins exporting with `exporter`
+  pub fn new(renderer: &Renderer, mut exporter: E) -> Self {
+    let (width, height) = renderer.settings.resolution;
+
+    let pixel_size = std::mem::size_of::<[u8; 4]>() as u32;
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    let unpadded_bytes_per_row = pixel_size * width;
+    let padding = (align - unpadded_bytes_per_row % align) % align;
+    let padded_bytes_per_row = unpadded_bytes_per_row + padding;
+
+    let buffer = renderer
+      .wgpu_device()
+      .create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Output Buffer"),
+        size: (padded_bytes_per_row * height) as wgpu::BufferAddress,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+      });
+
+    exporter.begin(renderer.settings);
+
+    Self {
+      exporter,
+      buffer,
+      unpadded_bytes_per_row,
+      padded_bytes_per_row,
+    }
+  }
+
+  /// Finishes the export after the last frame
+  pub fn end(self) {
+    self.exporter.end();
+  }
+}
+
+impl<E: Export> Output for ExportOutput<E> {
+  fn present(&mut self, renderer: &mut Renderer, _overlay: Vec<RenderEvent>) {
+    let (width, height) = renderer.settings.resolution;
+
+    let mut encoder = renderer
+      .device
+      .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Output Command Encoder"),
+      });
+
+    encoder.copy_texture_to_buffer(
+      wgpu::TexelCopyTextureInfo {
+        aspect: wgpu::TextureAspect::All,
+        texture: renderer.frame.texture(),
+        mip_level: 0,
+        origin: wgpu::Origin3d::ZERO,
+      },
+      wgpu::TexelCopyBufferInfo {
+        buffer: &self.buffer,
+        layout: wgpu::TexelCopyBufferLayout {
+          offset: 0,
+          bytes_per_row: Some(self.padded_bytes_per_row),
+          rows_per_image: Some(height),
+        },
+      },
+      wgpu::Extent3d {
+        width,
+        height,
+        depth_or_array_layers: 1,
+      },
+    );
+
+    renderer.queue.submit(std::iter::once(encoder.finish()));
+
+    let buffer_slice = self.buffer.slice(..);
+    let (tx, rx) = futures_intrusive::channel::shared::oneshot_channel();
+    buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
+      tx.send(result).unwrap();
+    });
+    renderer.device.poll(wgpu::Maintain::Wait);
+    let result = pollster::block_on(rx.receive()).unwrap();
+
+    match result {
+      Ok(()) => {
+        let padded_data = buffer_slice.get_mapped_range();
+        let data = padded_data
+          .chunks(self.padded_bytes_per_row as _)
+          .flat_map(|chunk| &chunk[..self.unpadded_bytes_per_row as _])
+          .copied()
+          .collect::<Vec<_>>();
+        drop(padded_data);
+        self.buffer.unmap();
+        self.exporter.push_frame(true, &data);
+      }
+      _ => panic!("Something went wrong while copying GPU buffer to RAM for encoding!"),
+    }
+  }
+}
@@ -0,0 +1,27 @@
+struct PresentUniform {
+  scale: vec2<f32>,
+  offset: vec2<f32>,
+  letterbox: vec4<f32>,
+};
+
+@group(1)
+@binding(0)
+var<uniform> present_uniform: PresentUniform;
+
+@group(1)
+@binding(1)
+var frame_sampler: sampler;
+
+@group(1)
+@binding(2)
+var frame_texture: texture_2d<f32>;
+
+@fragment
+fn fs_main(in: FullscreenOutput) -> @location(0) vec4<f32> {
+  // Window uv to frame uv
+  let uv = in.uv * present_uniform.scale + present_uniform.offset;
+  let color = textureSample(frame_texture, frame_sampler, uv);
+
+  let inside = all(uv >= vec2(0.0)) && all(uv <= vec2(1.0));
+  return select(present_uniform.letterbox, color, inside);
+}
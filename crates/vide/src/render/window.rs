@@ -0,0 +1,233 @@
+use std::sync::{Arc, Mutex};
+
+use wgpu::util::DeviceExt;
+
+use super::{render_target, screen_matrix, Output, RenderEvent, RenderTarget, Renderer};
+use crate::api::fullscreen::FullscreenPass;
+
+/// Where the frame is drawn in the window, see [`WindowOutput::set_viewport`]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+struct PresentUniform {
+  /// Window uv to frame uv
+  scale: [f32; 2],
+  offset: [f32; 2],
+  /// Color around the frame
+  letterbox: [f32; 4],
+}
+
+/// Shows frames in a window of any size, scaled and letterboxed. Overlays
+/// cover the whole window and are positioned in window pixels instead of
+/// video pixels.
+pub struct WindowOutput {
+  surface: wgpu::Surface<'static>,
+  config: wgpu::SurfaceConfiguration,
+  /// Window sized, the scaled frame and overlays are drawn here first since
+  /// the window's format can differ from the renderer's
+  target: RenderTarget,
+  present: FullscreenPass,
+  blit: FullscreenPass,
+  uniform: PresentUniform,
+  transform_buffer: wgpu::Buffer,
+  transform_bind_group: wgpu::BindGroup,
+}
+
+impl WindowOutput {
+  pub fn new(renderer: &Renderer, window: Arc<winit::window::Window>) -> Self {
+    let size = window.inner_size();
+    let size = (size.width.max(1), size.height.max(1));
+    let surface = renderer.instance.create_surface(window).unwrap();
+    assert!(
+      renderer.adapter.is_surface_supported(&surface),
+      "the graphics adapter can't draw to this window"
+    );
+
+    let capabilities = surface.get_capabilities(&renderer.adapter);
+    let config = wgpu::SurfaceConfiguration {
+      usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+      // Colors are rendered in linear light and have to be encoded to srgb,
+      // the blit does that itself if no format can
+      format: capabilities
+        .formats
+        .iter()
+        .copied()
+        .find(wgpu::TextureFormat::is_srgb)
+        .unwrap_or(capabilities.formats[0]),
+      width: size.0,
+      height: size.1,
+      present_mode: wgpu::PresentMode::Fifo,
+      alpha_mode: capabilities.alpha_modes[0],
+      desired_maximum_frame_latency: 2,
+      view_formats: vec![],
+    };
+    surface.configure(&renderer.device, &config);
+    if !config.format.is_srgb() {
+      log::info!(
+        "the window has no srgb format, encoding colors for {:?} in the shader",
+        config.format
+      );
+    }
+
+    let transform_buffer = renderer
+      .device
+      .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("Window Transform Buffer"),
+        contents: bytemuck::cast_slice(&[Into::<[[f32; 4]; 4]>::into(screen_matrix(size))]),
+        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+      });
+    let transform_bind_group = renderer
+      .device
+      .create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("Window Transform Bind Group"),
+        layout: &renderer.transform_bind_group_layout,
+        entries: &[wgpu::BindGroupEntry {
+          binding: 0,
+          resource: transform_buffer.as_entire_binding(),
+        }],
+      });
+
+    Self {
+      present: FullscreenPass::new(
+        renderer,
+        include_str!("present.wgsl"),
+        1,
+        std::mem::size_of::<PresentUniform>() as u64,
+        None,
+      ),
+      blit: FullscreenPass::with_format(
+        renderer,
+        &format!(
+          "const ENCODE_SRGB: bool = {};\n{}",
+          !config.format.is_srgb(),
+          include_str!("blit.wgsl")
+        ),
+        1,
+        0,
+        None,
+        config.format,
+      ),
+      target: render_target(&renderer.device, size),
+      uniform: PresentUniform {
+        scale: [1.0, 1.0],
+        offset: [0.0, 0.0],
+        letterbox: [0.0, 0.0, 0.0, 1.0],
+      },
+      surface,
+      config,
+      transform_buffer,
+      transform_bind_group,
+    }
+  }
+
+  /// Matches the window's new `size`, in physical pixels
+  pub fn resize(&mut self, renderer: &Renderer, size: (u32, u32)) {
+    if size.0 == 0 || size.1 == 0 || size == (self.config.width, self.config.height) {
+      return;
+    }
+
+    self.config.width = size.0;
+    self.config.height = size.1;
+    self.surface.configure(&renderer.device, &self.config);
+    self.target = render_target(&renderer.device, size);
+    renderer.queue.write_buffer(
+      &self.transform_buffer,
+      0,
+      bytemuck::cast_slice(&[Into::<[[f32; 4]; 4]>::into(screen_matrix(size))]),
+    );
+  }
+
+  /// Places the frame in the window: one pixel of the video covers `scale`
+  /// window pixels and its top left corner is at `offset` from the window's
+  /// top left corner. Everything around it is filled with `letterbox`, a
+  /// linear color
+  pub fn set_viewport(
+    &mut self,
+    renderer: &Renderer,
+    scale: f64,
+    offset: (f64, f64),
+    letterbox: [f32; 4],
+  ) {
+    let size = (
+      renderer.settings.resolution.0 as f64 * scale,
+      renderer.settings.resolution.1 as f64 * scale,
+    );
+    self.uniform = PresentUniform {
+      scale: [
+        (self.config.width as f64 / size.0) as f32,
+        (self.config.height as f64 / size.1) as f32,
+      ],
+      offset: [(-offset.0 / size.0) as f32, (-offset.1 / size.1) as f32],
+      letterbox,
+    };
+  }
+}
+
+impl Output for WindowOutput {
+  fn present(&mut self, renderer: &mut Renderer, overlay: Vec<RenderEvent>) {
+    let output = match self.surface.get_current_texture() {
+      Ok(output) => output,
+      // The window changed, skip this frame and try again with the next one
+      Err(wgpu::SurfaceError::Outdated | wgpu::SurfaceError::Lost) => {
+        self.surface.configure(&renderer.device, &self.config);
+        return;
+      }
+      Err(error) => panic!("failed to get the next window surface texture: {error}"),
+    };
+    let surface_view = output
+      .texture
+      .create_view(&wgpu::TextureViewDescriptor::default());
+
+    renderer.render_to_target(&self.target, wgpu::Color::BLACK, |renderer, pass| {
+      pass
+        .lock()
+        .unwrap()
+        .set_bind_group(0, &self.transform_bind_group, &[]);
+      self.present.render(
+        renderer,
+        pass.lock().unwrap(),
+        bytemuck::bytes_of(&self.uniform),
+        &[&renderer.frame],
+      );
+      renderer.render_events(pass, overlay);
+    });
+
+    let mut encoder = renderer
+      .device
+      .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Window Command Encoder"),
+      });
+
+    {
+      let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("Window Render Pass"),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+          view: &surface_view,
+          resolve_target: None,
+          ops: wgpu::Operations {
+            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+            store: wgpu::StoreOp::Store,
+          },
+        })],
+        depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+          view: &self.target.depth_view,
+          depth_ops: Some(wgpu::Operations {
+            load: wgpu::LoadOp::Clear(1.0),
+            store: wgpu::StoreOp::Store,
+          }),
+          stencil_ops: None,
+        }),
+        ..Default::default()
+      });
+
+      pass.set_bind_group(0, &self.transform_bind_group, &[]);
+
+      let pass_ref = Mutex::new(pass);
+      self
+        .blit
+        .render(renderer, pass_ref.lock().unwrap(), &[], &[&self.target]);
+    }
+
+    renderer.queue.submit(std::iter::once(encoder.finish()));
+    output.present();
+  }
+}
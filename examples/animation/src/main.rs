@@ -10,12 +10,19 @@ fn main() {
     ..Default::default()
   });
 
-  video.push_clip(
+  // The same animation in three color spaces, each starting a bit later
+  let rects = [
+    (-300.0, ColorSpace::LinearRgb),
+    (0.0, ColorSpace::Oklab),
+    (300.0, ColorSpace::Oklch),
+  ]
+  .map(|(x, color_space)| {
     Rect::builder()
-      .position((-300.0, 0.0))
+      .position((x, 0.0))
       .size((200.0, 150.0))
       .color(
         Animated::builder()
+          .color_space(color_space)
           .keyframe(Abs(0.0), ease::LINEAR, rgba8!(0xda, 0x00, 0x37, 0x00))
           .keyframe(Rel(0.3), ease::OUT_QUADRATIC, rgb8!(0xda, 0x00, 0x37))
           .hold(0.3)
@@ -23,57 +30,29 @@ fn main() {
           .build(),
       )
       .rounded(0.3) // 30%
-      .timing(1.0..5.0)
-      .build(),
-  );
-
-  video.push_clip(
-    Rect::builder()
-      .position((0.0, 0.0))
-      .size((200.0, 150.0))
-      .color(
-        Animated::builder()
-          .keyframe(Abs(0.0), ease::LINEAR, rgba8!(0xda, 0x00, 0x37, 0x00))
-          .keyframe(Rel(0.3), ease::OUT_QUADRATIC, rgb8!(0xda, 0x00, 0x37))
-          .hold(0.3)
-          .keyframe(Rel(0.3), ease::IN_QUADRATIC, rgb8!(0x00, 0xda, 0x37))
-          .build(),
-      )
-      .rounded(0.3) // 30%
-      .timing(1.0..5.0)
-      .build(),
-  );
-
-  video.push_clip(
-    Rect::builder()
-      .position((300.0, 0.0))
-      .size((200.0, 150.0))
-      .color(
-        Animated::builder()
-          .keyframe(Abs(0.0), ease::LINEAR, rgba8!(0xda, 0x00, 0x37, 0x00))
-          .keyframe(Rel(0.3), ease::OUT_QUADRATIC, rgb8!(0xda, 0x00, 0x37))
-          .hold(0.3)
-          .keyframe(Rel(0.3), ease::IN_QUADRATIC, rgb8!(0x00, 0xda, 0x37))
-          .build(),
-      )
-      .rounded(0.3) // 30%
-      .timing(1.0..5.0)
-      .build(),
-  );
+      .timing(0.0..4.0)
+  });
 
-  video.push_clip(
-    Rect::builder()
-      .position((0.0, 0.0))
-      .size(
-        Animated::builder()
-          .keyframe(Abs(0.0), ease::LINEAR, (0.0, 1080.0))
-          .keyframe(Rel(0.9), ease::OUT_EXPONENTIAL, (1920.0, 1080.0))
-          .build(),
-      )
-      .color(rgb8!(0x00, 0x37, 0xda))
-      .timing(1.0..5.0)
-      .build(),
-  );
+  let clips = Timeline::new()
+    .after(1.0)
+    .stagger(&Stagger::new(0.15), rects)
+    .with(
+      Rect::builder()
+        .position((0.0, 0.0))
+        .size(
+          Animated::builder()
+            .keyframe(Abs(0.0), ease::LINEAR, (0.0, 1080.0))
+            .keyframe(Rel(0.9), ease::OUT_EXPONENTIAL, (1920.0, 1080.0))
+            .build(),
+        )
+        .color(rgb8!(0x00, 0x37, 0xda))
+        .timing(0.0..4.0),
+    )
+    .build();
+
+  for clip in clips {
+    video.push_clip(clip.build());
+  }
 
   video.render(vide_export::quick_export::to("output.mp4"));
 }
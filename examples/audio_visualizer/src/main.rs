@@ -1,4 +1,4 @@
-use std::{env::args, time::Duration};
+use std::{env::args, rc::Rc, time::Duration};
 
 use spectrum_analyzer::{
   samples_fft_to_spectrum, scaling::scale_to_zero_to_one, windows::hann_window, FrequencyLimit,
@@ -54,17 +54,12 @@ fn main() {
 
   let bar_x_size = ALL_BARS_WIDTH / BARS as f32 - BAR_SEPERATION;
 
-  let mut builders: Vec<AnimatedBuilder<(f32, f32)>> = Vec::with_capacity(BARS);
-
-  for _ in 0..BARS {
-    let mut animation = Animated::builder();
-    animation.keyframe(Abs(0.0), ease::LINEAR, (bar_x_size, 0.0));
-    builders.push(animation);
-  }
-
   let freq_step = (MAX_FREQ - MIN_FREQ) / (BARS - 1) as f32;
   let samples_per_frame = sample_rate as usize / 60;
 
+  // One row of bar heights per frame, looked up by the bars instead of
+  // creating a keyframe per frame per bar
+  let mut heights: Vec<[f32; BARS]> = Vec::new();
   let mut previous_value = [0.0f32; BARS];
 
   for frame in 0..((duration.as_secs() + 1) * 60) {
@@ -73,6 +68,7 @@ fn main() {
 
     if channel_samples[0].len() < end {
       log::warn!("Lost frame {frame}");
+      heights.push(heights.last().copied().unwrap_or([2.0; BARS]));
       continue;
     }
 
@@ -85,6 +81,7 @@ fn main() {
     )
     .unwrap();
 
+    let mut row = [0.0; BARS];
     for bar in 0..BARS {
       let (_, value) = spectrum.freq_val_closest(bar as f32 * freq_step + MIN_FREQ);
 
@@ -92,24 +89,27 @@ fn main() {
       let value = (prev * 0.84).max(value.val() * 5000.0);
       previous_value[bar] = value;
 
-      builders[bar].keyframe(
-        Abs(frame),
-        ease::LINEAR,
-        (bar_x_size, (BAR_HEIGHT * (value / 5000.0)).max(2.0)),
-      );
+      row[bar] = (BAR_HEIGHT * (value / 5000.0)).max(2.0);
     }
+    heights.push(row);
   }
 
   log::info!("Building animations");
 
-  for (i, builder) in builders.into_iter().enumerate() {
+  let heights = Rc::new(heights);
+
+  for i in 0..BARS {
+    let heights = heights.clone();
     video.push_clip(
       Rect::builder()
         .position((
           (ALL_BARS_WIDTH * -0.5) + (bar_x_size + BAR_SEPERATION) * i as f32,
           0.0,
         ))
-        .size(builder.build())
+        .size(Animated::from_fn(move |time: Time| {
+          let frame = (time.clip_frame as usize).min(heights.len() - 1);
+          (bar_x_size, heights[frame][i])
+        }))
         .color(rgb8!(0x5f, 0xf2, 0xf0))
         .timing(duration.as_secs_f64()..7.0)
         .build(),
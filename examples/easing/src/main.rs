@@ -22,9 +22,13 @@ fn main() {
     ease::OUT_QUARTIC,
     ease::OUT_QUINTIC,
     ease::OUT_EXPONENTIAL,
+    ease::IN_OUT_CUBIC,
+    ease::IN_OUT_SINE,
     ease::IN_BACK,
     ease::OUT_BACK,
     ease::IN_OUT_BACK,
+    ease::OUT_ELASTIC,
+    ease::OUT_BOUNCE,
   ];
 
   let rect_size = 600.0 / easing_functions.len() as f32;